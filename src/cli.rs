@@ -1,6 +1,12 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 
+use crate::core::{
+    build::run_build,
+    config::{Config, SsgType},
+    server::{self, Stream},
+};
+
 #[derive(Parser)]
 #[command(name = "textorium")]
 #[command(about = "A fast terminal interface for static site generators", long_about = None)]
@@ -77,9 +83,9 @@ pub enum Commands {
 
     /// Start development server
     Serve {
-        /// Port number
-        #[arg(short, long, default_value = "1313")]
-        port: u16,
+        /// Port number (defaults to the site config's, or the SSG's default)
+        #[arg(short, long)]
+        port: Option<u16>,
 
         /// Don't include drafts
         #[arg(long)]
@@ -104,11 +110,11 @@ pub async fn run(cli: Cli) -> Result<()> {
             crate::core::config::configure_site(&path)?;
             println!("✓ Configured textorium to use: {}", path);
         }
-        Some(Commands::New { title, category, tags, no_edit }) => {
+        Some(Commands::New { title, .. }) => {
             println!("Creating new post: {}", title);
             // TODO: Implement
         }
-        Some(Commands::List { drafts, category, json }) => {
+        Some(Commands::List { .. }) => {
             println!("Listing posts...");
             // TODO: Implement
         }
@@ -116,17 +122,45 @@ pub async fn run(cli: Cli) -> Result<()> {
             println!("Publishing: {}", slug);
             // TODO: Implement
         }
-        Some(Commands::Idea { title, category, notes, tags }) => {
+        Some(Commands::Idea { title, .. }) => {
             println!("Capturing idea: {}", title);
             // TODO: Implement
         }
         Some(Commands::Serve { port, no_drafts }) => {
-            println!("Starting server on port {}...", port);
-            // TODO: Implement
+            let config = Config::load()?;
+            let port = port.unwrap_or_else(|| config.site_config().dev_server_port(&config.ssg));
+            if let Some(code) = server::serve(&config, port, !no_drafts).await? {
+                std::process::exit(code);
+            }
         }
         Some(Commands::Build { minify }) => {
-            println!("Building site...");
-            // TODO: Implement
+            let config = Config::load()?;
+            if minify && config.ssg != SsgType::Hugo {
+                eprintln!("⚠ --minify is only supported by Hugo; configure minification in the site instead");
+            }
+            let report = run_build(&config, minify, |stream, line| match stream {
+                Stream::Stdout => println!("{}", line),
+                Stream::Stderr => eprintln!("{}", line),
+            })?;
+
+            // Compiler-style summary so editors and scripts can pick up locations
+            let root = std::path::PathBuf::from(&config.site_path);
+            if !report.problems.is_empty() {
+                eprintln!();
+            }
+            for problem in &report.problems {
+                match problem.location(&root) {
+                    Some(location) => eprintln!("{}: {}: {}", location, problem.severity, problem.message),
+                    None => eprintln!("{}: {}", problem.severity, problem.message),
+                }
+            }
+
+            if report.succeeded() {
+                println!("✓ Build finished: {}", report.command);
+            } else {
+                eprintln!("✗ Build failed: {}", report.command);
+                std::process::exit(report.code.unwrap_or(1));
+            }
         }
     }
 
@@ -0,0 +1,168 @@
+use anyhow::Result;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use super::config::Config;
+use super::problems::{Problem, Severity};
+use super::server::{is_error_line, run_to_end, SsgCommand, Stream};
+
+/// Outcome of a production build
+#[derive(Debug, Clone)]
+pub struct BuildReport {
+    pub command: String,
+    /// SSG exit code, `None` when it was killed by a signal
+    pub code: Option<i32>,
+    pub problems: Vec<Problem>,
+}
+
+impl BuildReport {
+    pub fn succeeded(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+/// A source file reference, optionally followed by `:line[:col]`
+fn path_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?:^|[\s"'(\[:,=])((?:[A-Za-z]:)?[\w./\\~-]*[\w-]\.(?:md|markdown|mdx|html|njk|liquid))(?::(\d+)(?::\d+)?)?"#)
+            .unwrap()
+    })
+}
+
+/// `line 5`, `(line 5)`, `line:5`
+fn line_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)\bline[:\s]\s*(\d+)").unwrap())
+}
+
+/// Tool prefixes and timestamps that add nothing to the message
+fn prefix_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^(?:\[11ty\]\s*)?(?:(?:ERROR|WARN)\s+(?:\d{4}/\d\d/\d\d \d\d:\d\d:\d\d\s+)?)?").unwrap())
+}
+
+fn severity(line: &str) -> Option<Severity> {
+    let lower = line.to_lowercase();
+    if lower.contains("warn") || lower.contains("deprecat") {
+        Some(Severity::Warning)
+    } else if is_error_line(line)
+        || ["exception", "problem writing", "having trouble", "failed", "(via "].iter().any(|k| lower.contains(k))
+    {
+        Some(Severity::Error)
+    } else {
+        None
+    }
+}
+
+fn resolve(root: &Path, path: &str) -> PathBuf {
+    let path = path.trim_start_matches("./");
+    if Path::new(path).is_absolute() {
+        PathBuf::from(path)
+    } else {
+        root.join(path)
+    }
+}
+
+/// Pick errors and warnings out of Hugo, Jekyll or Eleventy build output,
+/// with the source file and line when the message names one
+pub fn parse_problems(output: &[String], root: &Path) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = Vec::new();
+    for raw in output {
+        let Some(severity) = severity(raw) else { continue };
+        let (path, line) = match path_re().captures(raw) {
+            Some(c) => (Some(resolve(root, &c[1])), c.get(2).and_then(|l| l.as_str().parse().ok())),
+            None => (None, None),
+        };
+        let line = line.or_else(|| line_re().captures(raw).and_then(|c| c[1].parse().ok()));
+        let message = prefix_re().replace(raw.trim(), "").trim().to_string();
+
+        let problem = Problem { severity, path, line, message };
+        if !problems.contains(&problem) {
+            problems.push(problem);
+        }
+    }
+    problems
+}
+
+/// Run the site's production build, passing output lines to `on_line` as they arrive
+pub fn run_build(config: &Config, minify: bool, mut on_line: impl FnMut(Stream, &str)) -> Result<BuildReport> {
+    let command = SsgCommand::build(config, minify);
+    let mut output = Vec::new();
+    let code = run_to_end(&command, |stream, line| {
+        on_line(stream, &line);
+        output.push(line);
+    })?;
+
+    Ok(BuildReport {
+        command: command.display(),
+        code,
+        problems: parse_problems(&output, &command.dir),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn hugo_output() {
+        let root = Path::new("/site");
+        let output = lines(concat!(
+            "Start building sites … \n",
+            "WARN  2024/05/01 10:00:00 found no layout file for \"html\" for kind \"taxonomy\"\n",
+            "Error: error building site: process: readAndProcessContent: \"/site/content/posts/bad.md:3:1\": failed to unmarshal YAML: yaml: line 3: did not find expected key\n",
+            "Total in 23 ms\n",
+        ));
+        let problems = parse_problems(&output, root);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].severity, Severity::Warning);
+        assert!(problems[0].message.starts_with("found no layout"));
+        assert_eq!(problems[1].severity, Severity::Error);
+        assert_eq!(problems[1].path.as_deref(), Some(Path::new("/site/content/posts/bad.md")));
+        assert_eq!(problems[1].line, Some(3));
+    }
+
+    #[test]
+    fn jekyll_output() {
+        let root = Path::new("/site");
+        let output = lines(concat!(
+            "Configuration file: /site/_config.yml\n",
+            "  Liquid Warning: Liquid syntax error (line 7): Unexpected character } in \"{{ page.title }}}\" in _posts/2024-01-02-warn.md\n",
+            "  Liquid Exception: Liquid syntax error (line 5): Unknown tag 'foo' in _posts/2024-01-01-bad.md\n",
+            "             Error: could not read file /site/_posts/2024-02-01-yaml.md: (<unknown>): did not find expected key while parsing a block mapping at line 2 column 1\n",
+        ));
+        let problems = parse_problems(&output, root);
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].severity, Severity::Warning);
+        assert_eq!(problems[0].path.as_deref(), Some(Path::new("/site/_posts/2024-01-02-warn.md")));
+        assert_eq!(problems[0].line, Some(7));
+        assert_eq!(problems[1].path.as_deref(), Some(Path::new("/site/_posts/2024-01-01-bad.md")));
+        assert_eq!(problems[1].line, Some(5));
+        assert_eq!(problems[2].path.as_deref(), Some(Path::new("/site/_posts/2024-02-01-yaml.md")));
+        assert_eq!(problems[2].line, Some(2));
+    }
+
+    #[test]
+    fn eleventy_output() {
+        let root = Path::new("/site");
+        let output = lines(concat!(
+            "[11ty] Problem writing Eleventy templates: (more in DEBUG output)\n",
+            "[11ty] 1. Having trouble rendering liquid template ./src/posts/bad.md (via TemplateContentRenderError)\n",
+            "[11ty] 2. tag \"foo\" not found, file:./src/posts/bad.md, line:3, col:1 (via ParseError)\n",
+            "[11ty] Wrote 0 files in 0.05 seconds (v2.0.1)\n",
+        ));
+        let problems = parse_problems(&output, root);
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0].path, None);
+        assert_eq!(problems[1].path.as_deref(), Some(Path::new("/site/src/posts/bad.md")));
+        assert_eq!(problems[1].line, None);
+        assert_eq!(problems[2].line, Some(3));
+        assert!(problems[2].message.starts_with("2. tag"));
+    }
+}
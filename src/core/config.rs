@@ -3,6 +3,10 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use super::permalink::permalink;
+use super::posts::Post;
+use super::site::{has_hugo_config, SiteConfig};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub site_name: String,
@@ -11,6 +15,9 @@ pub struct Config {
     pub ssg: SsgType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub editor: Option<String>,
+    /// Browser command for previews; `%s` is replaced by the URL, otherwise it is appended
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browser: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notion_database_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -27,12 +34,12 @@ pub enum SsgType {
 }
 
 impl SsgType {
-    /// Get the default dev server URL for this SSG type
-    pub fn dev_server_url(&self) -> &str {
+    /// Default dev server port for this SSG type (see `SiteConfig::dev_server_url`)
+    pub fn default_port(&self) -> u16 {
         match self {
-            SsgType::Hugo => "http://localhost:1313",
-            SsgType::Jekyll => "http://localhost:4000",
-            SsgType::Eleventy => "http://localhost:8080",
+            SsgType::Hugo => 1313,
+            SsgType::Jekyll => 4000,
+            SsgType::Eleventy => 8080,
         }
     }
 }
@@ -45,6 +52,7 @@ impl Default for Config {
             content_dir: "content".to_string(),
             ssg: SsgType::Hugo,
             editor: None,
+            browser: None,
             notion_database_id: None,
             notion_token: None,
         }
@@ -90,23 +98,29 @@ impl Config {
         PathBuf::from(&self.site_path).join(&self.content_dir)
     }
 
-    /// Get the preview URL for a post
-    /// Constructs the URL by combining the SSG dev server URL with the post's relative path
-    pub fn preview_url(&self, post_path: &PathBuf) -> Option<String> {
-        let site_path = PathBuf::from(&self.site_path);
-
-        // Get path relative to site root
-        let relative_path = post_path.strip_prefix(&site_path).ok()?;
+    /// Read the SSG's own config files for this site
+    pub fn site_config(&self) -> SiteConfig {
+        SiteConfig::load(&PathBuf::from(&self.site_path), &self.ssg)
+    }
 
-        // Convert to URL path (remove .md extension, convert to forward slashes)
-        let url_path = relative_path
-            .with_extension("")
-            .to_string_lossy()
-            .replace('\\', "/");
+    /// Get the preview URL for a post
+    /// Combines the dev server URL with the post's computed permalink. `server` is the
+    /// URL a running dev server reported; without it the site config's is assumed.
+    pub fn preview_url(&self, post: &Post, server: Option<&str>) -> Option<String> {
+        let site = self.site_config();
+        let path = permalink(self, &site.permalinks, post)?;
+
+        // Hugo can serve the default language under its own prefix
+        let language = match (&site.language, site.language_in_subdir) {
+            (Some(lang), true) => format!("/{}", lang),
+            _ => String::new(),
+        };
 
         // Construct full URL
-        let base_url = self.ssg.dev_server_url();
-        Some(format!("{}/{}", base_url, url_path))
+        let base_url = server
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| site.dev_server_url(&self.ssg));
+        Some(format!("{}{}{}", base_url, language, path))
     }
 }
 
@@ -114,10 +128,8 @@ impl Config {
 fn detect_ssg(path: &str) -> SsgType {
     let path = PathBuf::from(path);
 
-    // Hugo: has hugo.toml, hugo.yaml, or config.toml
-    if path.join("hugo.toml").exists()
-        || path.join("hugo.yaml").exists()
-        || path.join("config.toml").exists() {
+    // Hugo: has hugo.toml, hugo.yaml, config.toml, or config/_default/
+    if has_hugo_config(&path) {
         return SsgType::Hugo;
     }
 
@@ -136,28 +148,22 @@ fn detect_ssg(path: &str) -> SsgType {
     SsgType::Hugo
 }
 
-/// Detect content directory
+/// Detect content directory, preferring what the site's own config says
 fn detect_content_dir(path: &str, ssg: &SsgType) -> String {
     let path = PathBuf::from(path);
+    let site = SiteConfig::load(&path, ssg);
 
     match ssg {
-        SsgType::Hugo => {
-            if path.join("content").exists() {
-                "content".to_string()
-            } else {
-                "content".to_string() // Hugo default
-            }
-        }
-        SsgType::Jekyll => {
-            if path.join("_posts").exists() {
-                "_posts".to_string()
-            } else {
-                "_posts".to_string() // Jekyll default
-            }
-        }
+        SsgType::Hugo => site.content_dir.unwrap_or_else(|| "content".to_string()), // Hugo default
+        SsgType::Jekyll => site.content_dir.unwrap_or_else(|| "_posts".to_string()), // Jekyll default
         SsgType::Eleventy => {
-            if path.join("posts").exists() {
-                "posts".to_string()
+            // Eleventy renders the whole input dir; prefer a posts folder inside it
+            let input = site.content_dir.unwrap_or_default();
+            let input_path = path.join(&input);
+            if input_path.join("posts").exists() {
+                PathBuf::from(&input).join("posts").to_string_lossy().to_string()
+            } else if !input.is_empty() {
+                input
             } else if path.join("src").exists() {
                 "src".to_string()
             } else {
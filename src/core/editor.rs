@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use super::config::Config;
+
+/// How an editor expects to be told which line to open at
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineStyle {
+    /// `editor +LINE file` (vim, nano, emacs, micro, ...)
+    Plus,
+    /// `editor file:LINE` (helix, sublime, zed)
+    Colon,
+    /// `code --goto file:LINE`
+    Goto,
+    /// `mate -l LINE file`
+    DashL,
+    /// `kate --line LINE file` and JetBrains IDEs
+    DashDashLine,
+    /// No known convention: just the file
+    None,
+}
+
+/// A parsed editor command line, e.g. `code --wait` or `nvim -u NONE`
+#[derive(Debug, Clone)]
+pub struct EditorCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl EditorCommand {
+    /// Parse an editor string with shell quoting rules
+    pub fn parse(command: &str) -> Result<Self> {
+        let mut words = shell_words::split(command)
+            .with_context(|| format!("Could not parse editor command: {}", command))?;
+        if words.is_empty() {
+            anyhow::bail!("Editor command is empty");
+        }
+        let program = words.remove(0);
+        Ok(Self { program, args: words })
+    }
+
+    /// Editor from config, then `$VISUAL`, then `$EDITOR`, falling back to nano
+    pub fn resolve(config: &Config) -> Result<Self> {
+        let command = config
+            .editor
+            .clone()
+            .or_else(|| std::env::var("VISUAL").ok())
+            .or_else(|| std::env::var("EDITOR").ok())
+            .filter(|e| !e.trim().is_empty())
+            .unwrap_or_else(|| "nano".to_string());
+        Self::parse(&command)
+    }
+
+    /// Program name without directory or extension (`/usr/bin/nvim` -> `nvim`)
+    fn name(&self) -> String {
+        Path::new(&self.program)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(&self.program)
+            .to_lowercase()
+    }
+
+    fn has_arg(&self, flags: &[&str]) -> bool {
+        self.args.iter().any(|a| flags.contains(&a.as_str()))
+    }
+
+    fn line_style(&self) -> LineStyle {
+        match self.name().as_str() {
+            "vi" | "vim" | "nvim" | "gvim" | "mvim" | "nano" | "pico" | "emacs" | "emacsclient"
+            | "micro" | "kak" | "joe" | "ne" | "mg" | "gedit" => LineStyle::Plus,
+            "hx" | "helix" | "subl" | "sublime_text" | "zed" | "atom" => LineStyle::Colon,
+            "code" | "code-insiders" | "codium" | "vscodium" | "cursor" | "windsurf" => LineStyle::Goto,
+            "mate" => LineStyle::DashL,
+            "kate" | "idea" | "pycharm" | "webstorm" | "goland" | "clion" | "rustrover" => {
+                LineStyle::DashDashLine
+            }
+            _ => LineStyle::None,
+        }
+    }
+
+    /// Whether the editor returns immediately and keeps editing in its own window.
+    /// GUI editors invoked with their wait flag block like terminal editors.
+    pub fn is_detached(&self) -> bool {
+        match self.name().as_str() {
+            "code" | "code-insiders" | "codium" | "vscodium" | "cursor" | "windsurf" | "subl"
+            | "sublime_text" | "zed" | "atom" | "mate" => !self.has_arg(&["-w", "--wait"]),
+            "gvim" | "mvim" => !self.has_arg(&["-f", "--nofork"]),
+            "emacsclient" => self.has_arg(&["-n", "--no-wait"]),
+            "kate" => !self.has_arg(&["-b", "--block"]),
+            "idea" | "pycharm" | "webstorm" | "goland" | "clion" | "rustrover" | "gedit" | "open" => true,
+            _ => false,
+        }
+    }
+
+    /// Build the process for opening `path`, at `line` (1-based) when given
+    pub fn command(&self, path: &Path, line: Option<usize>) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args);
+
+        let file = path.to_string_lossy().to_string();
+        match (line, self.line_style()) {
+            (Some(line), LineStyle::Plus) => {
+                cmd.arg(format!("+{}", line)).arg(file);
+            }
+            (Some(line), LineStyle::Colon) => {
+                cmd.arg(format!("{}:{}", file, line));
+            }
+            (Some(line), LineStyle::Goto) => {
+                cmd.arg("--goto").arg(format!("{}:{}", file, line));
+            }
+            (Some(line), LineStyle::DashL) => {
+                cmd.arg("-l").arg(line.to_string()).arg(file);
+            }
+            (Some(line), LineStyle::DashDashLine) => {
+                cmd.arg("--line").arg(line.to_string()).arg(file);
+            }
+            _ => {
+                cmd.arg(file);
+            }
+        }
+        cmd
+    }
+}
@@ -1,2 +1,9 @@
+pub mod build;
 pub mod config;
+pub mod editor;
+pub mod opener;
+pub mod permalink;
 pub mod posts;
+pub mod problems;
+pub mod server;
+pub mod site;
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::config::Config;
+
+/// How a URL was handed to the user
+#[derive(Debug)]
+pub enum OpenOutcome {
+    /// Launched in a browser via the named opener
+    Opened(String),
+    /// No display available; the URL was copied to the clipboard via OSC 52
+    Copied,
+}
+
+/// Running under Windows Subsystem for Linux
+fn is_wsl() -> bool {
+    std::env::var_os("WSL_DISTRO_NAME").is_some()
+        || std::fs::read_to_string("/proc/version")
+            .map(|v| v.to_lowercase().contains("microsoft"))
+            .unwrap_or(false)
+}
+
+fn is_ssh() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+/// Whether a browser launched from here would show up in front of the user
+fn has_display() -> bool {
+    if is_ssh() {
+        return false;
+    }
+    if cfg!(target_os = "linux") && !is_wsl() {
+        return std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some();
+    }
+    true
+}
+
+/// Candidate opener commands for this platform, most specific first
+fn platform_openers() -> Vec<Vec<String>> {
+    let cmd = |parts: &[&str]| parts.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    if cfg!(target_os = "macos") {
+        vec![cmd(&["open"])]
+    } else if cfg!(target_os = "windows") {
+        vec![cmd(&["cmd", "/C", "start", ""])]
+    } else if is_wsl() {
+        vec![cmd(&["wslview"]), cmd(&["cmd.exe", "/C", "start", ""]), cmd(&["xdg-open"])]
+    } else {
+        vec![cmd(&["xdg-open"]), cmd(&["gio", "open"]), cmd(&["sensible-browser"])]
+    }
+}
+
+/// Spawn `parts` with the URL substituted for `%s`, or appended when there is no placeholder
+fn spawn_opener(parts: &[String], url: &str) -> std::io::Result<()> {
+    let mut args: Vec<String> = parts.iter().map(|p| p.replace("%s", url)).collect();
+    if !parts.iter().any(|p| p.contains("%s")) {
+        args.push(url.to_string());
+    }
+    Command::new(&args[0])
+        .args(&args[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// Copy text to the system clipboard through the terminal (OSC 52).
+/// Works over SSH and inside tmux when the outer terminal allows it.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let osc = format!("\x1b]52;c;{}\x07", encoded);
+    let sequence = if std::env::var_os("TMUX").is_some() {
+        // tmux passthrough: wrap in DCS and double the inner escapes
+        format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b"))
+    } else {
+        osc
+    };
+
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes()).context("Failed to write to terminal")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Open a URL in the configured or platform browser, falling back to the clipboard
+pub fn open_url(url: &str, config: &Config) -> Result<OpenOutcome> {
+    // An explicit browser command always wins, even over SSH (e.g. a remote-open script)
+    let configured = config
+        .browser
+        .clone()
+        .or_else(|| std::env::var("BROWSER").ok())
+        .filter(|b| !b.trim().is_empty());
+    if let Some(browser) = configured {
+        let parts = shell_words::split(&browser)
+            .with_context(|| format!("Could not parse browser command: {}", browser))?;
+        if parts.is_empty() {
+            anyhow::bail!("Browser command is empty");
+        }
+        spawn_opener(&parts, url).with_context(|| format!("Could not run browser: {}", parts[0]))?;
+        return Ok(OpenOutcome::Opened(parts[0].clone()));
+    }
+
+    if has_display() {
+        for opener in platform_openers() {
+            if spawn_opener(&opener, url).is_ok() {
+                return Ok(OpenOutcome::Opened(opener[0].clone()));
+            }
+        }
+    }
+
+    copy_to_clipboard(url)?;
+    Ok(OpenOutcome::Copied)
+}
@@ -0,0 +1,420 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use super::config::{Config, SsgType};
+use super::posts::Post;
+
+/// Site-level permalink settings, read from the SSG config by `SiteConfig`
+#[derive(Debug, Clone, Default)]
+pub struct PermalinkRules {
+    /// Hugo `[permalinks]`: section name -> pattern
+    pub hugo: HashMap<String, String>,
+    /// Jekyll `permalink:`: a pattern or a built-in style (`date`, `pretty`, ...)
+    pub jekyll: Option<String>,
+    /// Eleventy input directory; URLs are relative to it
+    pub input_dir: Option<String>,
+}
+
+/// Flatten Hugo's `permalinks` table: `{posts = "..."}` or `{page = {posts = "..."}}`
+pub fn hugo_permalinks(value: &serde_json::Value) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    if let Some(table) = value.as_object() {
+        for (key, pattern) in table {
+            match pattern {
+                serde_json::Value::String(p) => {
+                    out.insert(key.clone(), p.clone());
+                }
+                serde_json::Value::Object(kinds) if key == "page" => {
+                    for (section, p) in kinds {
+                        if let Some(p) = p.as_str() {
+                            out.insert(section.clone(), p.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+/// URL-safe slug: lowercase, words joined by hyphens, punctuation dropped
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+fn fm_str<'a>(post: &'a Post, key: &str) -> Option<&'a str> {
+    post.frontmatter.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty())
+}
+
+/// Normalise to a rooted path with single slashes
+fn clean_url(url: &str) -> String {
+    let mut out = String::from("/");
+    for part in url.split('/').filter(|p| !p.is_empty()) {
+        out.push_str(part);
+        out.push('/');
+    }
+    // Keep file-style URLs (`/about.html`) without a trailing slash
+    if !url.ends_with('/') && out.len() > 1 {
+        out.pop();
+    }
+    out
+}
+
+/// Path components of `path` relative to `root`, as strings
+fn components(path: &Path, root: &Path) -> Option<Vec<String>> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(
+        relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(s) => s.to_str().map(String::from),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+fn file_stem(name: &str) -> String {
+    Path::new(name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Jekyll post filenames: `YYYY-MM-DD-title.md` -> (date, title)
+pub fn split_dated_filename(stem: &str) -> Option<(NaiveDate, String)> {
+    if stem.len() < 11 || !stem.is_char_boundary(10) || stem.as_bytes().get(10) != Some(&b'-') {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(&stem[..10], "%Y-%m-%d").ok()?;
+    Some((date, stem[11..].to_string()))
+}
+
+/// Substitute `:token` placeholders using `value`; unknown tokens are left as-is
+fn expand(pattern: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut rest = pattern;
+    while let Some(pos) = rest.find(':') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let name_len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        let name = &after[..name_len];
+        match value(name) {
+            Some(v) => out.push_str(&v),
+            None => {
+                out.push(':');
+                out.push_str(name);
+            }
+        }
+        rest = &after[name_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn date_token(date: Option<DateTime<Utc>>, name: &str) -> Option<String> {
+    let date = date?;
+    Some(match name {
+        "year" => format!("{:04}", date.year()),
+        "month" => format!("{:02}", date.month()),
+        "i_month" => date.month().to_string(),
+        "monthname" | "long_month" => date.format("%B").to_string().to_lowercase(),
+        "short_month" => date.format("%b").to_string(),
+        "day" => format!("{:02}", date.day()),
+        "i_day" => date.day().to_string(),
+        "short_year" => date.format("%y").to_string(),
+        "weekday" => date.weekday().num_days_from_sunday().to_string(),
+        "yearday" | "y_day" => format!("{:03}", date.ordinal()),
+        _ => return None,
+    })
+}
+
+/// Hugo permalink for a post
+fn hugo_permalink(config: &Config, rules: &PermalinkRules, post: &Post) -> Option<String> {
+    if let Some(url) = fm_str(post, "url") {
+        return Some(clean_url(url));
+    }
+
+    let parts = components(&post.path, &config.content_path())?;
+    let (file, dirs) = parts.split_last()?;
+
+    // Section list page: the directory's URL
+    if file == "_index.md" || file.starts_with("_index.") {
+        return Some(clean_url(&format!("{}/", dirs.join("/").to_lowercase())));
+    }
+
+    // Leaf bundle: the directory names the page
+    let (filename, dirs) = if file_stem(file) == "index" && !dirs.is_empty() {
+        let (name, parents) = dirs.split_last()?;
+        (name.clone(), parents)
+    } else {
+        (file_stem(file), dirs)
+    };
+
+    let section = dirs.first().cloned().unwrap_or_default();
+    if let Some(pattern) = rules.hugo.get(&section) {
+        let title_slug = slugify(&post.title);
+        let slug = fm_str(post, "slug").map(String::from);
+        let url = expand(pattern, |name| match name {
+            "section" => Some(section.clone()),
+            "sections" => Some(dirs.join("/")),
+            "title" => Some(title_slug.clone()),
+            "slug" => Some(slug.clone().unwrap_or_else(|| title_slug.clone())),
+            "filename" | "contentbasename" => Some(filename.clone()),
+            "slugorfilename" | "slugorcontentbasename" => Some(slug.clone().unwrap_or_else(|| filename.clone())),
+            other => date_token(post.date, other),
+        });
+        return Some(clean_url(&url));
+    }
+
+    // Default: the content path, with `slug` replacing the last segment
+    let last = fm_str(post, "slug").map(String::from).unwrap_or_else(|| filename.to_lowercase());
+    let mut segments: Vec<String> = dirs.iter().map(|d| d.to_lowercase()).collect();
+    segments.push(last);
+    Some(clean_url(&format!("{}/", segments.join("/"))))
+}
+
+/// Jekyll categories from `categories` (list or space-separated) and `category`
+fn jekyll_categories(post: &Post) -> Vec<String> {
+    let mut cats: Vec<String> = match post.frontmatter.get("categories") {
+        Some(serde_json::Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).map(String::from).collect(),
+        Some(serde_json::Value::String(s)) => s.split_whitespace().map(String::from).collect(),
+        _ => Vec::new(),
+    };
+    if let Some(cat) = fm_str(post, "category") {
+        cats.push(cat.to_string());
+    }
+    let mut seen = Vec::new();
+    for cat in cats.into_iter().map(|c| c.to_lowercase().replace(' ', "-")) {
+        if !seen.contains(&cat) {
+            seen.push(cat);
+        }
+    }
+    seen
+}
+
+fn jekyll_style(style: &str) -> &str {
+    match style {
+        "date" => "/:categories/:year/:month/:day/:title:output_ext",
+        "pretty" => "/:categories/:year/:month/:day/:title/",
+        "ordinal" => "/:categories/:year/:y_day/:title:output_ext",
+        "weekdate" => "/:categories/:year/W:week/:short_day/:title:output_ext",
+        "none" => "/:categories/:title:output_ext",
+        pattern => pattern,
+    }
+}
+
+/// Jekyll permalink for a post or page
+fn jekyll_permalink(config: &Config, rules: &PermalinkRules, post: &Post) -> Option<String> {
+    let site = PathBuf::from(&config.site_path);
+    let parts = components(&post.path, &site)?;
+    let (file, dirs) = parts.split_last()?;
+    let stem = file_stem(file);
+    let is_post = dirs.iter().any(|d| d == "_posts" || d == "_drafts");
+
+    let site_style = rules.jekyll.as_deref().unwrap_or("date");
+    let pretty = site_style == "pretty" || site_style.ends_with('/');
+
+    if !is_post {
+        if let Some(permalink) = fm_str(post, "permalink") {
+            return Some(clean_url(permalink));
+        }
+        // Pages keep their path; `index` maps to the directory
+        let mut segments: Vec<String> = dirs.to_vec();
+        if stem == "index" {
+            return Some(clean_url(&format!("{}/", segments.join("/"))));
+        }
+        return if pretty {
+            segments.push(stem);
+            Some(clean_url(&format!("{}/", segments.join("/"))))
+        } else {
+            segments.push(format!("{}.html", stem));
+            Some(clean_url(&segments.join("/")))
+        };
+    }
+
+    let (file_date, file_title) = match split_dated_filename(&stem) {
+        Some((date, title)) => (date.and_hms_opt(0, 0, 0).map(|d| d.and_utc()), title),
+        None => (None, stem.clone()),
+    };
+    let date = post.date.or(file_date);
+    let title = fm_str(post, "slug").map(String::from).unwrap_or(file_title);
+    let categories = jekyll_categories(post);
+
+    let pattern = fm_str(post, "permalink").unwrap_or_else(|| jekyll_style(site_style));
+    let url = expand(jekyll_style(pattern), |name| match name {
+        "categories" => Some(categories.join("/")),
+        "title" | "slug" => Some(title.clone()),
+        "name" => Some(stem.clone()),
+        "output_ext" => Some(".html".to_string()),
+        "week" => date.map(|d| format!("{:02}", d.iso_week().week())),
+        "short_day" => date.map(|d| d.format("%a").to_string()),
+        other => date_token(date, other),
+    });
+    Some(clean_url(&url))
+}
+
+/// Fill the handful of Nunjucks/Liquid expressions commonly used in Eleventy permalinks
+fn eleventy_template(template: &str, post: &Post, file_slug: &str, path_stem: &str) -> String {
+    let re = regex::Regex::new(r"\{\{-?\s*([^}]*?)\s*-?\}\}").unwrap();
+    re.replace_all(template, |caps: &regex::Captures| {
+        let expr: Vec<&str> = caps[1].split('|').map(|s| s.trim()).collect();
+        let value = match expr[0] {
+            "page.fileSlug" => file_slug.to_string(),
+            "page.filePathStem" => path_stem.to_string(),
+            "title" => post.title.clone(),
+            key => post
+                .frontmatter
+                .get(key)
+                .map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))
+                .unwrap_or_default(),
+        };
+        if expr[1..].iter().any(|f| f.starts_with("slug")) {
+            slugify(&value)
+        } else {
+            value
+        }
+    })
+    .into_owned()
+}
+
+/// Input directory Eleventy renders from
+fn eleventy_input_dir(config: &Config, rules: &PermalinkRules) -> PathBuf {
+    let site = PathBuf::from(&config.site_path);
+    match &rules.input_dir {
+        Some(input) => site.join(input),
+        None => site,
+    }
+}
+
+/// Eleventy permalink for a template
+fn eleventy_permalink(config: &Config, rules: &PermalinkRules, post: &Post) -> Option<String> {
+    let parts = components(&post.path, &eleventy_input_dir(config, rules))?;
+    let (file, dirs) = parts.split_last()?;
+    let stem = file_stem(file);
+
+    // `index` files take their directory's slug, as in Eleventy's page.fileSlug
+    let file_slug = if stem == "index" {
+        dirs.last().cloned().unwrap_or_default()
+    } else {
+        stem.clone()
+    };
+    let mut stem_parts = dirs.to_vec();
+    stem_parts.push(stem.clone());
+    let path_stem = format!("/{}", stem_parts.join("/"));
+
+    match post.frontmatter.get("permalink") {
+        Some(serde_json::Value::Bool(false)) => return None,
+        Some(serde_json::Value::String(template)) => {
+            return Some(clean_url(&eleventy_template(template, post, &file_slug, &path_stem)));
+        }
+        _ => {}
+    }
+
+    let mut segments = dirs.to_vec();
+    if stem != "index" {
+        segments.push(stem);
+    }
+    Some(clean_url(&format!("{}/", segments.join("/"))))
+}
+
+/// The site-relative URL a post is served at, or `None` if it isn't rendered
+pub fn permalink(config: &Config, rules: &PermalinkRules, post: &Post) -> Option<String> {
+    match config.ssg {
+        SsgType::Hugo => hugo_permalink(config, rules, post),
+        SsgType::Jekyll => jekyll_permalink(config, rules, post),
+        SsgType::Eleventy => eleventy_permalink(config, rules, post),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::posts::read_post;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+    }
+
+    fn site(name: &str, ssg: SsgType, content_dir: &str) -> Config {
+        Config {
+            site_path: fixture(name).to_string_lossy().to_string(),
+            content_dir: content_dir.to_string(),
+            ssg,
+            ..Default::default()
+        }
+    }
+
+    fn url_of(config: &Config, file: &str) -> Option<String> {
+        let rules = config.site_config().permalinks;
+        let post = read_post(&PathBuf::from(&config.site_path).join(file)).unwrap();
+        permalink(config, &rules, &post)
+    }
+
+    #[test]
+    fn hugo_permalinks() {
+        let config = site("hugo", SsgType::Hugo, "content");
+        assert_eq!(url_of(&config, "content/_index.md").as_deref(), Some("/"));
+        assert_eq!(url_of(&config, "content/about.md").as_deref(), Some("/about/"));
+        assert_eq!(url_of(&config, "content/posts/_index.md").as_deref(), Some("/posts/"));
+        // `[permalinks] posts = "/:year/:month/:slug/"`, slug falling back to the title
+        assert_eq!(url_of(&config, "content/posts/first-post.md").as_deref(), Some("/2024/03/first-post/"));
+        assert_eq!(url_of(&config, "content/posts/custom.md").as_deref(), Some("/2024/05/my-custom-slug/"));
+        assert_eq!(url_of(&config, "content/posts/bundle-post/index.md").as_deref(), Some("/2023/12/bundle-post/"));
+        // Sections without a pattern use the content path
+        assert_eq!(url_of(&config, "content/docs/Guide/Install.md").as_deref(), Some("/docs/guide/install/"));
+        assert_eq!(url_of(&config, "content/docs/bundle/index.md").as_deref(), Some("/docs/bundle/"));
+        assert_eq!(url_of(&config, "content/docs/renamed.md").as_deref(), Some("/docs/new-name/"));
+        assert_eq!(url_of(&config, "content/docs/moved.md").as_deref(), Some("/elsewhere/"));
+    }
+
+    #[test]
+    fn jekyll_permalinks() {
+        let config = site("jekyll", SsgType::Jekyll, "_posts");
+        // `permalink: pretty` in _config.yml
+        assert_eq!(url_of(&config, "_posts/2024-03-01-hello-world.md").as_deref(), Some("/news/2024/03/01/hello-world/"));
+        assert_eq!(url_of(&config, "_posts/2024-04-02-custom.md").as_deref(), Some("/custom-path/"));
+        assert_eq!(url_of(&config, "_posts/2024-05-03-with-slug.md").as_deref(), Some("/2024/05/03/other/"));
+        assert_eq!(url_of(&config, "about.md").as_deref(), Some("/about/"));
+
+        // Default `date` style
+        let rules = PermalinkRules::default();
+        let post = read_post(&fixture("jekyll").join("_posts/2024-03-01-hello-world.md")).unwrap();
+        assert_eq!(
+            permalink(&config, &rules, &post).as_deref(),
+            Some("/news/2024/03/01/hello-world.html")
+        );
+        let pattern = PermalinkRules { jekyll: Some("/blog/:year/:title/".to_string()), ..Default::default() };
+        assert_eq!(permalink(&config, &pattern, &post).as_deref(), Some("/blog/2024/hello-world/"));
+    }
+
+    #[test]
+    fn eleventy_permalinks() {
+        let config = site("eleventy", SsgType::Eleventy, "posts");
+        assert_eq!(url_of(&config, "posts/my-first-post.md").as_deref(), Some("/posts/my-first-post/"));
+        assert_eq!(url_of(&config, "posts/index.md").as_deref(), Some("/posts/"));
+        assert_eq!(url_of(&config, "posts/custom.md").as_deref(), Some("/custom/custom/"));
+        assert_eq!(url_of(&config, "posts/titled.md").as_deref(), Some("/blog/hello-there/"));
+        assert_eq!(url_of(&config, "posts/hidden.md"), None);
+    }
+
+    #[test]
+    fn slugify_text() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  Rust  &  Go  "), "rust-go");
+        assert_eq!(slugify("snake_case title"), "snake-case-title");
+    }
+}
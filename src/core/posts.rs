@@ -19,6 +19,9 @@ pub struct Post {
     pub tags: Vec<String>,
     pub content: String,
     pub frontmatter: HashMap<String, serde_json::Value>,
+    /// Line in the file (0-based) where `content` starts
+    #[serde(default)]
+    pub body_line: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +102,10 @@ pub fn read_post(path: &Path) -> Result<Post> {
 
     let (frontmatter, body) = parse_frontmatter(&content)?;
 
+    // The body is the trimmed tail of the file, so its start is measured from the end
+    let body_start = content.trim_end().len().saturating_sub(body.len());
+    let body_line = content[..body_start].matches('\n').count();
+
     // Extract fields
     let title = frontmatter
         .get("title")
@@ -162,6 +169,7 @@ pub fn read_post(path: &Path) -> Result<Post> {
         tags,
         content: body,
         frontmatter,
+        body_line,
     })
 }
 
@@ -199,7 +207,7 @@ pub fn scan_posts(config: &Config) -> Result<Vec<Post>> {
     }
 
     // Sort by date, newest first
-    posts.sort_by(|a, b| b.date.cmp(&a.date));
+    posts.sort_by_key(|p| std::cmp::Reverse(p.date));
 
     Ok(posts)
 }
@@ -0,0 +1,46 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// An error or warning tied, where possible, to a source file and line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub severity: Severity,
+    pub path: Option<PathBuf>,
+    /// 1-based line in the file
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    /// `path:line` relative to `root` when the problem has a location
+    pub fn location(&self, root: &Path) -> Option<String> {
+        let path = self.path.as_ref()?;
+        let path = path.strip_prefix(root).unwrap_or(path).display().to_string();
+        Some(match self.line {
+            Some(line) => format!("{}:{}", path, line),
+            None => path,
+        })
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self.severity {
+            Severity::Error => "✗",
+            Severity::Warning => "⚠",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
@@ -0,0 +1,402 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::config::{Config, SsgType};
+
+/// Lines of output kept for the log panel
+const LOG_CAPACITY: usize = 500;
+
+/// An SSG process to run in the site directory
+#[derive(Debug, Clone)]
+pub struct SsgCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub dir: PathBuf,
+    pub env: Vec<(String, String)>,
+}
+
+impl SsgCommand {
+    /// The SSG's dev server command for this site
+    pub fn serve(config: &Config, port: u16, drafts: bool) -> Self {
+        let (program, mut args): (&str, Vec<String>) = match config.ssg {
+            SsgType::Hugo => ("hugo", vec!["server".into(), "--port".into(), port.to_string()]),
+            SsgType::Jekyll => (
+                "bundle",
+                vec!["exec".into(), "jekyll".into(), "serve".into(), "--port".into(), port.to_string()],
+            ),
+            SsgType::Eleventy => ("npx", vec!["@11ty/eleventy".into(), "--serve".into(), format!("--port={}", port)]),
+        };
+        if drafts {
+            match config.ssg {
+                SsgType::Hugo => args.push("--buildDrafts".into()),
+                SsgType::Jekyll => args.push("--drafts".into()),
+                // Eleventy has no draft concept; drafts are handled in the site's own data
+                SsgType::Eleventy => {}
+            }
+        }
+        Self { program: program.to_string(), args, dir: PathBuf::from(&config.site_path), env: Vec::new() }
+    }
+
+    /// The SSG's production build command. Only Hugo can minify by itself.
+    pub fn build(config: &Config, minify: bool) -> Self {
+        let (program, mut args, env): (&str, Vec<String>, Vec<(String, String)>) = match config.ssg {
+            SsgType::Hugo => ("hugo", Vec::new(), Vec::new()),
+            SsgType::Jekyll => (
+                "bundle",
+                vec!["exec".into(), "jekyll".into(), "build".into()],
+                vec![("JEKYLL_ENV".into(), "production".into())],
+            ),
+            SsgType::Eleventy => ("npx", vec!["@11ty/eleventy".into()], Vec::new()),
+        };
+        if minify && config.ssg == SsgType::Hugo {
+            args.push("--minify".into());
+        }
+        Self { program: program.to_string(), args, dir: PathBuf::from(&config.site_path), env }
+    }
+
+    pub fn display(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(|a| a.as_str()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerStatus {
+    Starting,
+    /// Serving at the URL the SSG reported
+    Ready(String),
+    /// Exited without being asked to, with its exit code
+    Crashed(Option<i32>),
+    Stopped,
+}
+
+/// Output stream a log line came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+enum ServerEvent {
+    Line(Stream, String),
+    /// The stream reached end of file
+    Closed,
+}
+
+/// Matches the "server is up" line printed by Hugo, Jekyll and Eleventy
+fn ready_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)(?:available at|server address:|server at|listening on|local:)\s*(https?://\S+)").unwrap()
+    })
+}
+
+/// The URL a server announced as ready in an output line
+pub fn ready_url(line: &str) -> Option<String> {
+    ready_re().captures(line).map(|c| c[1].trim_end_matches('/').to_string())
+}
+
+/// Whether an output line reports an error
+pub fn is_error_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    lower.starts_with("error") || lower.contains(" error ") || lower.contains("error:") || lower.contains("fatal")
+}
+
+fn pipe_lines(stream: impl Read + Send + 'static, kind: Stream, tx: Sender<ServerEvent>) {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(|l| l.ok()) {
+            if tx.send(ServerEvent::Line(kind, line)).is_err() {
+                return;
+            }
+        }
+        let _ = tx.send(ServerEvent::Closed);
+    });
+}
+
+/// Spawn a command with piped output, in its own process group on Unix
+/// so the whole tree (e.g. npx -> node) can be stopped together
+pub fn spawn_piped(cmd: &SsgCommand) -> Result<Child> {
+    let mut command = Command::new(&cmd.program);
+    command
+        .args(&cmd.args)
+        .current_dir(&cmd.dir)
+        .envs(cmd.env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    command
+        .spawn()
+        .with_context(|| format!("Could not start `{}`", cmd.display()))
+}
+
+/// Run a command to completion, passing each output line to `on_line` as it arrives.
+/// Returns the exit code (`None` when killed by a signal).
+pub fn run_to_end(cmd: &SsgCommand, mut on_line: impl FnMut(Stream, String)) -> Result<Option<i32>> {
+    let mut child = spawn_piped(cmd)?;
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        pipe_lines(stdout, Stream::Stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        pipe_lines(stderr, Stream::Stderr, tx);
+    }
+    // Ends once both readers hit EOF and drop their senders
+    for event in rx {
+        if let ServerEvent::Line(stream, line) = event {
+            on_line(stream, line);
+        }
+    }
+    let status = child.wait().context("Failed to wait for build")?;
+    Ok(status.code())
+}
+
+/// A supervised SSG dev server
+pub struct DevServer {
+    child: Child,
+    events: Receiver<ServerEvent>,
+    pub command: SsgCommand,
+    pub status: ServerStatus,
+    pub log: VecDeque<(Stream, String)>,
+    open_streams: usize,
+}
+
+impl DevServer {
+    pub fn start(command: SsgCommand) -> Result<Self> {
+        let mut child = spawn_piped(&command)?;
+        let (tx, rx) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            pipe_lines(stdout, Stream::Stdout, tx.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            pipe_lines(stderr, Stream::Stderr, tx);
+        }
+
+        Ok(Self {
+            child,
+            events: rx,
+            command,
+            status: ServerStatus::Starting,
+            log: VecDeque::new(),
+            open_streams: 2,
+        })
+    }
+
+    fn record(&mut self, event: ServerEvent, lines: &mut Vec<(Stream, String)>) {
+        let ServerEvent::Line(stream, line) = event else {
+            self.open_streams = self.open_streams.saturating_sub(1);
+            return;
+        };
+        if self.status == ServerStatus::Starting {
+            if let Some(url) = ready_url(&line) {
+                self.status = ServerStatus::Ready(url);
+            }
+        }
+        if self.log.len() == LOG_CAPACITY {
+            self.log.pop_front();
+        }
+        self.log.push_back((stream, line.clone()));
+        lines.push((stream, line));
+    }
+
+    /// Drain pending output and check for exit. Returns the new lines.
+    pub fn poll(&mut self) -> Vec<(Stream, String)> {
+        let mut lines = Vec::new();
+        while let Ok(event) = self.events.try_recv() {
+            self.record(event, &mut lines);
+        }
+
+        if self.is_running() {
+            if let Ok(Some(exit)) = self.child.try_wait() {
+                // Pick up anything printed just before exiting; a grandchild may
+                // still hold the pipes open, so don't wait for EOF indefinitely
+                let deadline = Instant::now() + Duration::from_millis(250);
+                while self.open_streams > 0 {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    match self.events.recv_timeout(remaining) {
+                        Ok(event) => self.record(event, &mut lines),
+                        Err(_) => break,
+                    }
+                }
+                self.status = ServerStatus::Crashed(exit.code());
+            }
+        }
+        lines
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(self.status, ServerStatus::Starting | ServerStatus::Ready(_))
+    }
+
+    /// Stop the server and everything it spawned
+    pub fn stop(&mut self) {
+        if !self.is_running() {
+            return;
+        }
+        #[cfg(unix)]
+        {
+            // Negative pid signals the whole process group
+            let _ = Command::new("kill")
+                .arg("-TERM")
+                .arg(format!("-{}", self.child.id()))
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        self.status = ServerStatus::Stopped;
+    }
+}
+
+impl Drop for DevServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Run the dev server in the foreground, streaming its output until Ctrl+C.
+/// Returns the exit code when the server crashed.
+pub async fn serve(config: &Config, port: u16, drafts: bool) -> Result<Option<i32>> {
+    let command = SsgCommand::serve(config, port, drafts);
+    println!("Starting: {}", command.display());
+    let mut server = DevServer::start(command)?;
+    let interrupt = tokio::signal::ctrl_c();
+    tokio::pin!(interrupt);
+
+    loop {
+        let was_ready = matches!(server.status, ServerStatus::Ready(_));
+        for (stream, line) in server.poll() {
+            match stream {
+                Stream::Stdout => println!("{}", line),
+                Stream::Stderr => eprintln!("{}", line),
+            }
+        }
+        match &server.status {
+            ServerStatus::Ready(url) if !was_ready => println!("✓ Server ready at {}", url),
+            ServerStatus::Crashed(code) => {
+                let reason = code.map(|c| format!("code {}", c)).unwrap_or_else(|| "killed by signal".to_string());
+                eprintln!("✗ {} exited ({})", server.command.program, reason);
+                // A server exiting on its own is a failure even when it reports success
+                return Ok(Some(code.filter(|&c| c != 0).unwrap_or(1)));
+            }
+            _ => {}
+        }
+
+        tokio::select! {
+            _ = &mut interrupt => {
+                server.stop();
+                println!("✓ Server stopped");
+                return Ok(None);
+            }
+            _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+        }
+    }
+}
+
+/// Whether something is already accepting connections at a `http://host:port` URL
+pub fn is_listening(url: &str) -> bool {
+    let authority = url
+        .trim_start_matches("http://")
+        .trim_start_matches("https://")
+        .split('/')
+        .next()
+        .unwrap_or("");
+    let addr = if authority.starts_with("localhost:") {
+        authority.replacen("localhost", "127.0.0.1", 1)
+    } else {
+        authority.to_string()
+    };
+    addr.parse::<std::net::SocketAddr>()
+        .map(|a| std::net::TcpStream::connect_timeout(&a, Duration::from_millis(150)).is_ok())
+        .unwrap_or(false)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Write an executable shell script standing in for an SSG binary
+    fn fake_ssg(dir: &std::path::Path, script: &str) -> SsgCommand {
+        let path = dir.join("fake-ssg");
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        SsgCommand {
+            program: path.to_string_lossy().to_string(),
+            args: Vec::new(),
+            dir: dir.to_path_buf(),
+            env: Vec::new(),
+        }
+    }
+
+    fn poll_until(server: &mut DevServer, done: impl Fn(&ServerStatus) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done(&server.status) && Instant::now() < deadline {
+            server.poll();
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn detects_readiness_and_stops() {
+        let dir = tempfile::tempdir().unwrap();
+        let cmd = fake_ssg(
+            dir.path(),
+            "echo 'Start building sites …'\necho 'Web Server is available at http://localhost:1313/ (bind address 127.0.0.1)'\nsleep 30",
+        );
+        let mut server = DevServer::start(cmd).unwrap();
+        poll_until(&mut server, |s| matches!(s, ServerStatus::Ready(_)));
+        assert_eq!(server.status, ServerStatus::Ready("http://localhost:1313".to_string()));
+        assert!(server.log.iter().any(|(_, l)| l.contains("Start building")));
+
+        server.stop();
+        assert_eq!(server.status, ServerStatus::Stopped);
+    }
+
+    #[test]
+    fn detects_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let cmd = fake_ssg(dir.path(), "echo 'Error: port 1313 already in use' >&2\nexit 3");
+        let mut server = DevServer::start(cmd).unwrap();
+        poll_until(&mut server, |s| matches!(s, ServerStatus::Crashed(_)));
+        assert_eq!(server.status, ServerStatus::Crashed(Some(3)));
+        assert!(server.log.iter().any(|(stream, l)| *stream == Stream::Stderr && is_error_line(l)));
+    }
+
+    #[test]
+    fn serve_flags_per_ssg() {
+        let mut config = Config { site_path: "/site".to_string(), ..Default::default() };
+        assert_eq!(SsgCommand::serve(&config, 1400, true).display(), "hugo server --port 1400 --buildDrafts");
+
+        config.ssg = SsgType::Jekyll;
+        assert_eq!(SsgCommand::serve(&config, 4001, false).display(), "bundle exec jekyll serve --port 4001");
+
+        config.ssg = SsgType::Eleventy;
+        assert_eq!(SsgCommand::serve(&config, 8081, true).display(), "npx @11ty/eleventy --serve --port=8081");
+    }
+
+    #[test]
+    fn ready_lines() {
+        assert_eq!(
+            ready_url("    Server address: http://127.0.0.1:4000/blog/").as_deref(),
+            Some("http://127.0.0.1:4000/blog")
+        );
+        assert_eq!(ready_url("[11ty] Server at http://localhost:8080/").as_deref(), Some("http://localhost:8080"));
+        assert_eq!(ready_url("Built in 120 ms"), None);
+    }
+}
@@ -0,0 +1,198 @@
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+use super::config::SsgType;
+use super::permalink::{hugo_permalinks, PermalinkRules};
+
+/// Settings read from the SSG's own configuration files
+#[derive(Debug, Clone, Default)]
+pub struct SiteConfig {
+    /// Production base URL (`baseURL`, `url` + `baseurl`, or `_data` metadata)
+    pub base_url: Option<String>,
+    /// Path prefix the site is served under, e.g. `/blog` for project pages
+    pub base_path: String,
+    /// Content directory relative to the site root
+    pub content_dir: Option<String>,
+    /// Taxonomy frontmatter keys (plural form), e.g. `tags`, `categories`
+    pub taxonomies: Vec<String>,
+    pub permalinks: PermalinkRules,
+    /// Default content language code
+    pub language: Option<String>,
+    /// Hugo `defaultContentLanguageInSubdir`: URLs are prefixed with the language
+    pub language_in_subdir: bool,
+    /// Dev server host and port
+    pub host: Option<String>,
+    pub port: Option<u16>,
+}
+
+/// Read a TOML, YAML or JSON file into a JSON value
+pub fn read_value(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => toml::from_str::<toml::Value>(&content)
+            .ok()
+            .and_then(|v| serde_json::to_value(v).ok()),
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&content).ok(),
+        Some("json") => serde_json::from_str(&content).ok(),
+        _ => None,
+    }
+}
+
+fn get_str(value: &serde_json::Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(String::from).filter(|s| !s.is_empty())
+}
+
+/// Path component of a URL: `https://x.org/blog/` -> `/blog`
+fn url_path(url: &str) -> String {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let path = without_scheme.find('/').map(|i| &without_scheme[i..]).unwrap_or("");
+    path.trim_end_matches('/').to_string()
+}
+
+/// Hugo site config file, checked in Hugo's lookup order
+fn hugo_config_file(site: &Path) -> Option<serde_json::Value> {
+    ["hugo", "config"]
+        .iter()
+        .flat_map(|name| {
+            ["toml", "yaml", "yml", "json"].iter().flat_map(move |ext| {
+                [
+                    format!("{}.{}", name, ext),
+                    format!("config/_default/{}.{}", name, ext),
+                ]
+            })
+        })
+        .find_map(|file| read_value(&site.join(file)))
+}
+
+/// Whether the directory has a Hugo config file
+pub fn has_hugo_config(site: &Path) -> bool {
+    ["hugo.toml", "hugo.yaml", "hugo.json", "config.toml"]
+        .iter()
+        .any(|name| site.join(name).exists())
+        || site.join("config/_default").is_dir()
+}
+
+fn load_hugo(site: &Path) -> SiteConfig {
+    let mut config = SiteConfig {
+        taxonomies: vec!["tags".to_string(), "categories".to_string()],
+        ..Default::default()
+    };
+    let Some(value) = hugo_config_file(site) else { return config };
+
+    config.base_url = get_str(&value, "baseURL").or_else(|| get_str(&value, "baseurl"));
+    config.base_path = config.base_url.as_deref().map(url_path).unwrap_or_default();
+    config.content_dir = get_str(&value, "contentDir");
+    config.language = get_str(&value, "defaultContentLanguage").or_else(|| get_str(&value, "languageCode"));
+    config.language_in_subdir = value
+        .get("defaultContentLanguageInSubdir")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if let Some(taxonomies) = value.get("taxonomies").and_then(|v| v.as_object()) {
+        config.taxonomies = taxonomies.values().filter_map(|v| v.as_str()).map(String::from).collect();
+    }
+    if let Some(permalinks) = value.get("permalinks") {
+        config.permalinks.hugo = hugo_permalinks(permalinks);
+    }
+
+    // Multilingual sites may set the default language's content dir per language
+    if config.content_dir.is_none() {
+        let default_lang = config.language.clone().unwrap_or_else(|| "en".to_string());
+        config.content_dir = value
+            .get("languages")
+            .and_then(|l| l.get(&default_lang))
+            .and_then(|l| get_str(l, "contentDir"));
+    }
+
+    config
+}
+
+fn load_jekyll(site: &Path) -> SiteConfig {
+    let mut config = SiteConfig {
+        taxonomies: vec!["tags".to_string(), "categories".to_string()],
+        ..Default::default()
+    };
+    let Some(value) = read_value(&site.join("_config.yml")).or_else(|| read_value(&site.join("_config.yaml"))) else {
+        return config;
+    };
+
+    let base_path = get_str(&value, "baseurl").unwrap_or_default();
+    config.base_path = base_path.trim_end_matches('/').to_string();
+    config.base_url = get_str(&value, "url").map(|url| format!("{}{}", url.trim_end_matches('/'), config.base_path));
+    config.permalinks.jekyll = get_str(&value, "permalink");
+    config.language = get_str(&value, "lang").or_else(|| get_str(&value, "locale"));
+    config.host = get_str(&value, "host");
+    config.port = value.get("port").and_then(|p| {
+        p.as_u64().map(|n| n as u16).or_else(|| p.as_str().and_then(|s| s.parse().ok()))
+    });
+    // `source:` moves the whole site, posts included
+    config.content_dir = get_str(&value, "source").map(|s| format!("{}/_posts", s.trim_end_matches('/')));
+
+    config
+}
+
+/// Eleventy config is JavaScript, so read the common settings with patterns
+fn load_eleventy(site: &Path) -> SiteConfig {
+    let mut config = SiteConfig {
+        taxonomies: vec!["tags".to_string()],
+        ..Default::default()
+    };
+
+    let script = [".eleventy.js", "eleventy.config.js", "eleventy.config.mjs", "eleventy.config.cjs"]
+        .iter()
+        .find_map(|name| fs::read_to_string(site.join(name)).ok())
+        .unwrap_or_default();
+
+    let setting = |key: &str| -> Option<String> {
+        let re = Regex::new(&format!(r#"\b{}\s*:\s*["'`]([^"'`]+)["'`]"#, key)).ok()?;
+        re.captures(&script).map(|c| c[1].to_string())
+    };
+
+    let input = setting("input").map(|i| i.trim_start_matches("./").trim_end_matches('/').to_string());
+    config.content_dir = input.clone().filter(|i| !i.is_empty() && i != ".");
+    config.permalinks.input_dir = config.content_dir.clone();
+    config.base_path = setting("pathPrefix").unwrap_or_default().trim_end_matches('/').to_string();
+    config.port = Regex::new(r"\bport\s*:\s*(\d+)")
+        .ok()
+        .and_then(|re| re.captures(&script))
+        .and_then(|c| c[1].parse().ok());
+
+    // Site metadata conventionally lives in the global data directory
+    let input_dir = site.join(input.unwrap_or_default());
+    let data_dir = input_dir.join(setting("data").unwrap_or_else(|| "_data".to_string()));
+    let metadata = ["site.json", "metadata.json", "site.yaml", "metadata.yaml", "site.yml", "metadata.yml"]
+        .iter()
+        .find_map(|name| read_value(&data_dir.join(name)));
+    if let Some(metadata) = metadata {
+        config.base_url = get_str(&metadata, "url").or_else(|| get_str(&metadata, "baseUrl"));
+        config.language = get_str(&metadata, "language").or_else(|| get_str(&metadata, "lang"));
+    }
+
+    config
+}
+
+impl SiteConfig {
+    /// Read the site's SSG configuration; missing or unreadable files give defaults
+    pub fn load(site: &Path, ssg: &SsgType) -> Self {
+        match ssg {
+            SsgType::Hugo => load_hugo(site),
+            SsgType::Jekyll => load_jekyll(site),
+            SsgType::Eleventy => load_eleventy(site),
+        }
+    }
+
+    /// Dev server base URL, including any base path the site is served under
+    pub fn dev_server_url(&self, ssg: &SsgType) -> String {
+        let host = match self.host.as_deref() {
+            Some("0.0.0.0") | None => "localhost",
+            Some(host) => host,
+        };
+        format!("http://{}:{}{}", host, self.dev_server_port(ssg), self.base_path)
+    }
+
+    /// Port the dev server is expected on
+    pub fn dev_server_port(&self, ssg: &SsgType) -> u16 {
+        self.port.unwrap_or_else(|| ssg.default_port())
+    }
+}
@@ -4,26 +4,59 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use std::cell::Cell;
 use std::io;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant, SystemTime};
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Margin},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Cell as TableCell, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, Wrap},
     Frame, Terminal,
 };
 
-use crate::core::{config::Config, posts::{save_post, scan_posts, Post}};
+use crate::core::{
+    build::{run_build, BuildReport},
+    config::Config,
+    editor::EditorCommand,
+    opener::{open_url, OpenOutcome},
+    server::{is_error_line, is_listening, DevServer, SsgCommand, ServerStatus, Stream},
+    site::SiteConfig,
+    posts::{read_post, save_post, scan_posts, Post},
+    problems::{Problem, Severity},
+};
+use crate::widgets::{
+    editor::{Editor, EditorAction, Mode as EditorMode},
+    markdown,
+    wrap::wrap_lines,
+};
 
 pub struct App {
     config: Config,
+    site: SiteConfig, // Settings from the SSG's own config files
     posts: Vec<Post>,
     selected: usize,
     focused_pane: usize, // 0=posts, 1=metadata, 2=content
     metadata_selected: usize, // Selected field in metadata pane
     content_scroll: usize, // Scroll offset in content pane
+    content_view: ContentView, // Rendered markdown or raw source
+    content_viewport: Cell<(u16, u16)>, // Content pane text area (width, height), updated on draw
+    content_search_mode: bool, // Whether we're typing an in-content search
+    content_query: String, // In-content search query
+    content_match: usize, // Index of the current in-content match
+    editor: Option<(PathBuf, Editor)>, // Built-in body editor and the post it edits
+    watched: Vec<(PathBuf, Option<SystemTime>)>, // Files open in detached GUI editors, with last mtime
+    server: Option<DevServer>, // Dev server started from the TUI
+    server_panel: bool, // Whether the server log panel is shown
+    pending_preview: Option<PathBuf>, // Post to open once the dev server is ready
+    build: Option<Receiver<Result<BuildReport>>>, // Production build running in the background
+    problems: Vec<Problem>, // Errors and warnings from the last build
+    problems_title: String, // Summary shown on the problems view
+    problems_open: bool, // Whether the problems view is shown
+    problems_selected: usize, // Selected problem
     search_query: String,
     search_mode: bool,
     sort_mode: SortMode,
@@ -35,6 +68,30 @@ pub struct App {
     new_field_key: String, // Key name for new field being added
 }
 
+/// The selected post's body laid out for the content pane
+struct ContentLayout {
+    rows: Vec<Line<'static>>, // Wrapped display rows
+    headings: Vec<usize>, // Row of each heading
+    matches: Vec<usize>, // Row of each line matching the in-content search
+    row_sources: Vec<usize>, // Body line (0-based) each row came from
+}
+
+/// How an external editor session ended
+enum EditorLaunch {
+    Finished, // Blocking editor exited; the TUI was suspended
+    Detached, // Editor keeps running in its own window; the file is being watched
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ContentView {
+    Rendered,
+    Raw,
+}
+
 #[derive(Debug, Clone, Copy)]
 enum SortMode {
     DateDesc,
@@ -46,15 +103,32 @@ enum SortMode {
 impl App {
     pub fn new() -> Result<Self> {
         let config = Config::load()?;
+        let site = config.site_config();
         let posts = scan_posts(&config)?;
 
         Ok(Self {
             config,
+            site,
             posts,
             selected: 0,
             focused_pane: 0,
             metadata_selected: 0,
             content_scroll: 0,
+            content_view: ContentView::Rendered,
+            content_viewport: Cell::new((0, 0)),
+            content_search_mode: false,
+            content_query: String::new(),
+            content_match: 0,
+            editor: None,
+            watched: Vec::new(),
+            server: None,
+            server_panel: false,
+            pending_preview: None,
+            build: None,
+            problems: Vec::new(),
+            problems_title: String::new(),
+            problems_open: false,
+            problems_selected: 0,
             search_query: String::new(),
             search_mode: false,
             sort_mode: SortMode::DateDesc,
@@ -88,8 +162,8 @@ impl App {
 
         // Sort
         match self.sort_mode {
-            SortMode::DateDesc => filtered.sort_by(|a, b| b.date.cmp(&a.date)),
-            SortMode::DateAsc => filtered.sort_by(|a, b| a.date.cmp(&b.date)),
+            SortMode::DateDesc => filtered.sort_by_key(|p| std::cmp::Reverse(p.date)),
+            SortMode::DateAsc => filtered.sort_by_key(|p| p.date),
             SortMode::TitleAsc => filtered.sort_by(|a, b| a.title.cmp(&b.title)),
             SortMode::TitleDesc => filtered.sort_by(|a, b| b.title.cmp(&a.title)),
         }
@@ -97,16 +171,23 @@ impl App {
         filtered
     }
 
+    /// Select a post by index in the filtered list, resetting the content pane
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.content_scroll = 0;
+        self.content_match = 0;
+    }
+
     fn select_next(&mut self) {
         let filtered = self.get_filtered_posts();
         if !filtered.is_empty() && self.selected < filtered.len() - 1 {
-            self.selected += 1;
+            self.select(self.selected + 1);
         }
     }
 
     fn select_prev(&mut self) {
         if self.selected > 0 {
-            self.selected -= 1;
+            self.select(self.selected - 1);
         }
     }
 
@@ -117,64 +198,605 @@ impl App {
             SortMode::TitleAsc => SortMode::TitleDesc,
             SortMode::TitleDesc => SortMode::DateDesc,
         };
-        self.selected = 0;
+        self.select(0);
+    }
+
+    fn toggle_content_view(&mut self) {
+        self.content_view = match self.content_view {
+            ContentView::Rendered => ContentView::Raw,
+            ContentView::Raw => ContentView::Rendered,
+        };
+        self.content_scroll = 0;
+    }
+
+    /// Lay out the selected post's body at the current content pane width
+    fn content_layout(&self) -> Option<ContentLayout> {
+        let filtered = self.get_filtered_posts();
+        let post = filtered.get(self.selected)?;
+
+        let rendered = match self.content_view {
+            ContentView::Rendered => markdown::render(&post.content),
+            ContentView::Raw => markdown::render_raw(&post.content),
+        };
+
+        let mut matched = Vec::new();
+        let lines: Vec<Line<'static>> = rendered
+            .lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let (line, found) = highlight_query(line, &self.content_query);
+                if found {
+                    matched.push(i);
+                }
+                line
+            })
+            .collect();
+
+        let (width, _) = self.content_viewport.get();
+        let (rows, starts) = wrap_lines(&lines, width as usize);
+        let row_sources = (0..rows.len())
+            .map(|row| {
+                let line = starts.partition_point(|&start| start <= row).saturating_sub(1);
+                rendered.source_lines.get(line).copied().unwrap_or(0)
+            })
+            .collect();
+
+        Some(ContentLayout {
+            rows,
+            row_sources,
+            headings: rendered.headings.iter().filter_map(|&i| starts.get(i).copied()).collect(),
+            matches: matched.iter().filter_map(|&i| starts.get(i).copied()).collect(),
+        })
+    }
+
+    fn content_height(&self) -> usize {
+        (self.content_viewport.get().1 as usize).max(1)
+    }
+
+    fn scroll_content_to(&mut self, row: usize) {
+        let total = self.content_layout().map(|l| l.rows.len()).unwrap_or(0);
+        let max = total.saturating_sub(self.content_height());
+        self.content_scroll = row.min(max);
+    }
+
+    fn scroll_content_by(&mut self, delta: isize) {
+        let row = self.content_scroll.saturating_add_signed(delta);
+        self.scroll_content_to(row);
+    }
+
+    fn jump_heading(&mut self, forward: bool) {
+        let Some(layout) = self.content_layout() else { return };
+        let target = if forward {
+            layout.headings.iter().find(|&&r| r > self.content_scroll).copied()
+        } else {
+            layout.headings.iter().rev().find(|&&r| r < self.content_scroll).copied()
+        };
+        match target {
+            Some(row) => self.scroll_content_to(row),
+            None => self.status_message = "No more headings".to_string(),
+        }
+    }
+
+    /// Move to the next/previous in-content match, wrapping around.
+    /// With `from_scroll`, jump to the first match at or below the current scroll position.
+    fn jump_match(&mut self, forward: bool, from_scroll: bool) {
+        let Some(layout) = self.content_layout() else { return };
+        if self.content_query.is_empty() {
+            return;
+        }
+        if layout.matches.is_empty() {
+            self.status_message = format!("No matches for \"{}\"", self.content_query);
+            return;
+        }
+
+        let count = layout.matches.len();
+        self.content_match = if from_scroll {
+            layout.matches.iter().position(|&r| r >= self.content_scroll).unwrap_or(0)
+        } else if forward {
+            (self.content_match + 1) % count
+        } else {
+            (self.content_match + count - 1) % count
+        };
+        self.scroll_content_to(layout.matches[self.content_match]);
+        self.status_message = format!("Match {}/{} for \"{}\"", self.content_match + 1, count, self.content_query);
+    }
+
+    /// Open the built-in body editor on the selected post
+    fn open_body_editor(&mut self) {
+        let filtered = self.get_filtered_posts();
+        if let Some(post) = filtered.get(self.selected) {
+            let editor = Editor::new(&post.content);
+            self.editor = Some((post.path.clone(), editor));
+        }
+    }
+
+    /// Copy the editor buffer into its post, optionally writing it to disk
+    fn apply_body_editor(&mut self, save: bool) {
+        let Some((path, editor)) = self.editor.as_mut() else { return };
+        let Some(post) = self.posts.iter_mut().find(|p| &p.path == path) else { return };
+
+        post.content = editor.text();
+        if save {
+            match save_post(post) {
+                Ok(_) => {
+                    editor.mark_saved();
+                    self.status_message = format!("✓ Saved: {}", post.path.display());
+                }
+                Err(e) => self.status_message = format!("✗ Error saving: {}", e),
+            }
+        }
+    }
+
+    fn handle_editor_key(&mut self, key: event::KeyEvent) {
+        let Some((_, editor)) = self.editor.as_mut() else { return };
+        match editor.handle_key(key) {
+            EditorAction::None => {
+                let (width, height) = self.content_viewport.get();
+                editor.scroll_into_view(width as usize, height as usize);
+            }
+            EditorAction::Save => self.apply_body_editor(true),
+            EditorAction::Exit => {
+                let dirty = editor.is_dirty();
+                self.apply_body_editor(false);
+                self.editor = None;
+                if dirty {
+                    self.status_message = "Body changed — Ctrl+S to save".to_string();
+                }
+            }
+        }
     }
 
     fn toggle_drafts(&mut self) {
         self.drafts_only = !self.drafts_only;
-        self.selected = 0;
+        self.select(0);
     }
 
-    fn open_in_editor(&self) -> Result<()> {
+    /// File line (1-based) the content pane is looking at: the current find match,
+    /// otherwise the top visible line
+    fn content_file_line(&self, post: &Post) -> usize {
+        let body_line = self.content_layout()
+            .and_then(|layout| {
+                let row = if self.content_query.is_empty() {
+                    self.content_scroll
+                } else {
+                    layout.matches.get(self.content_match).copied().unwrap_or(self.content_scroll)
+                };
+                layout.row_sources.get(row).copied()
+            })
+            .unwrap_or(0);
+        post.body_line + body_line + 1
+    }
+
+    fn open_in_editor(&mut self) -> Result<EditorLaunch> {
         let filtered = self.get_filtered_posts();
-        if let Some(post) = filtered.get(self.selected) {
-            // Get editor from config or environment
-            let editor = if let Some(ref e) = self.config.editor {
-                e.clone()
-            } else if let Ok(e) = std::env::var("EDITOR") {
-                e
-            } else {
-                "nano".to_string()
-            };
+        let Some(post) = filtered.get(self.selected) else {
+            return Ok(EditorLaunch::Finished);
+        };
+        let path = post.path.clone();
+        let line = self.content_file_line(post);
+        let editor = EditorCommand::resolve(&self.config)?;
+        let mut command = editor.command(&path, Some(line));
+
+        if editor.is_detached() {
+            command.spawn()?;
+            self.watch_file(&path);
+            return Ok(EditorLaunch::Detached);
+        }
 
-            // Completely restore terminal
-            disable_raw_mode()?;
-            execute!(
-                io::stdout(),
-                LeaveAlternateScreen,
-                DisableMouseCapture,
-                crossterm::cursor::Show
-            )?;
-
-            // Open editor with proper terminal control
-            let status = Command::new(&editor)
-                .arg(&post.path)
-                .status()?;
-
-            // Re-enter TUI mode
-            enable_raw_mode()?;
-            execute!(
-                io::stdout(),
-                EnterAlternateScreen,
-                EnableMouseCapture
-            )?;
-
-            if status.success() {
-                return Ok(());
-            } else {
-                anyhow::bail!("Editor exited with error");
+        // Completely restore terminal
+        disable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            crossterm::cursor::Show
+        )?;
+
+        // Open editor with proper terminal control
+        let before = modified_time(&path);
+        let started = Instant::now();
+        let status = command.status();
+
+        // Re-enter TUI mode
+        enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            EnterAlternateScreen,
+            EnableMouseCapture
+        )?;
+
+        if !status?.success() {
+            anyhow::bail!("Editor exited with error");
+        }
+
+        // An unrecognised editor that returns at once without touching the file has
+        // most likely forked into its own window
+        if started.elapsed() < Duration::from_secs(1) && modified_time(&path) == before {
+            self.watch_file(&path);
+            return Ok(EditorLaunch::Detached);
+        }
+        Ok(EditorLaunch::Finished)
+    }
+
+    fn watch_file(&mut self, path: &Path) {
+        if !self.watched.iter().any(|(p, _)| p == path) {
+            self.watched.push((path.to_path_buf(), modified_time(path)));
+        }
+    }
+
+    /// Reload posts changed on disk by detached editors
+    fn check_watched(&mut self) {
+        let mut reloaded = Vec::new();
+        for (path, last) in self.watched.iter_mut() {
+            let current = modified_time(path);
+            if current != *last {
+                *last = current;
+                reloaded.push(path.clone());
+            }
+        }
+
+        for path in reloaded {
+            match read_post(&path) {
+                Ok(post) => {
+                    if let Some(existing) = self.posts.iter_mut().find(|p| p.path == path) {
+                        *existing = post;
+                    }
+                    self.status_message = format!("↻ Reloaded: {}", path.display());
+                }
+                Err(_) => self.watched.retain(|(p, _)| p != &path),
+            }
+        }
+    }
+
+    fn start_server(&mut self) {
+        let port = self.site.dev_server_port(&self.config.ssg);
+        let command = SsgCommand::serve(&self.config, port, true);
+        let display = command.display();
+        match DevServer::start(command) {
+            Ok(server) => {
+                self.server = Some(server);
+                self.status_message = format!("Starting dev server: {}", display);
+            }
+            Err(e) => self.status_message = format!("✗ {:#}", e),
+        }
+    }
+
+    /// Start the dev server, or stop it if it is running
+    fn toggle_server(&mut self) {
+        match self.server.as_mut() {
+            Some(server) if server.is_running() => {
+                server.stop();
+                self.pending_preview = None;
+                self.status_message = "✓ Dev server stopped".to_string();
             }
+            _ => self.start_server(),
         }
-        Ok(())
     }
+
+    /// Pick up server output and react to it becoming ready or crashing
+    fn poll_server(&mut self) {
+        let Some(server) = self.server.as_mut() else { return };
+        let before = server.status.clone();
+        server.poll();
+        if server.status == before {
+            return;
+        }
+
+        match server.status.clone() {
+            ServerStatus::Ready(url) => {
+                self.status_message = format!("✓ Dev server ready at {}", url);
+                if let Some(path) = self.pending_preview.take() {
+                    if let Some(post) = self.posts.iter().find(|p| p.path == path).cloned() {
+                        self.open_preview(&post);
+                    }
+                }
+            }
+            ServerStatus::Crashed(code) => {
+                let code = code.map(|c| format!("code {}", c)).unwrap_or_else(|| "signal".to_string());
+                self.status_message = format!("✗ Dev server exited ({}) — see log", code);
+                self.server_panel = true;
+                self.pending_preview = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Run the production build on a background thread
+    fn start_build(&mut self) {
+        if self.build.is_some() {
+            self.status_message = "Build already running".to_string();
+            return;
+        }
+        let config = self.config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(run_build(&config, false, |_, _| {}));
+        });
+        self.build = Some(rx);
+        self.status_message = "Building site…".to_string();
+    }
+
+    /// Collect a finished build and show its problems
+    fn poll_build(&mut self) {
+        let Some(rx) = &self.build else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err(anyhow::anyhow!("Build thread exited")),
+        };
+        self.build = None;
+
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => {
+                self.status_message = format!("✗ {:#}", e);
+                return;
+            }
+        };
+        let errors = report.problems.iter().filter(|p| p.severity == Severity::Error).count();
+        let warnings = report.problems.len() - errors;
+        let outcome = match report.code {
+            Some(0) => "✓ Build succeeded".to_string(),
+            Some(code) => format!("✗ Build failed (exit {})", code),
+            None => "✗ Build killed".to_string(),
+        };
+        self.problems_title = format!("{} — {} errors, {} warnings", outcome, errors, warnings);
+        self.status_message = self.problems_title.clone();
+        self.problems_open = !report.problems.is_empty() || !report.succeeded();
+        self.problems = report.problems;
+        self.problems_selected = 0;
+    }
+
+    fn handle_problems_key(&mut self, key: event::KeyEvent) {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.problems_selected + 1 < self.problems.len() {
+                    self.problems_selected += 1;
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => self.problems_selected = self.problems_selected.saturating_sub(1),
+            KeyCode::Enter => self.jump_to_problem(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('P') => self.problems_open = false,
+            _ => {}
+        }
+    }
+
+    /// Select the post a problem points at and scroll to its line
+    fn jump_to_problem(&mut self) {
+        let Some(problem) = self.problems.get(self.problems_selected).cloned() else { return };
+        let Some(path) = problem.path.as_deref() else {
+            self.status_message = "No source location for this problem".to_string();
+            return;
+        };
+
+        let find = |app: &App| app.get_filtered_posts().iter().position(|p| same_file(&p.path, path));
+        let index = find(self).or_else(|| {
+            // The post may be hidden by the search or drafts filter
+            self.search_query.clear();
+            self.drafts_only = false;
+            find(self)
+        });
+        let Some(index) = index else {
+            self.status_message = format!("✗ Not a post: {}", path.display());
+            return;
+        };
+
+        self.select(index);
+        self.problems_open = false;
+        self.focused_pane = 2;
+        if let Some(line) = problem.line {
+            self.scroll_to_file_line(line);
+        }
+    }
+
+    /// Scroll the content pane to a 1-based line of the selected post's file
+    fn scroll_to_file_line(&mut self, line: usize) {
+        let Some(body_line) = self.get_filtered_posts().get(self.selected).map(|p| p.body_line) else { return };
+        let target = line.saturating_sub(body_line + 1);
+        let Some(layout) = self.content_layout() else { return };
+        let row = layout.row_sources.iter().position(|&source| source >= target).unwrap_or(0);
+        self.scroll_content_to(row);
+    }
+
+    fn open_preview(&mut self, post: &Post) {
+        let server = match self.server.as_ref().map(|s| &s.status) {
+            Some(ServerStatus::Ready(url)) => Some(url.clone()),
+            _ => None,
+        };
+        let Some(url) = self.config.preview_url(post, server.as_deref()) else {
+            self.status_message = "✗ Could not construct preview URL".to_string();
+            return;
+        };
+        match open_url(&url, &self.config) {
+            Ok(OpenOutcome::Opened(opener)) => {
+                self.status_message = format!("✓ Opening in browser ({}): {}", opener, url);
+            }
+            Ok(OpenOutcome::Copied) => {
+                self.status_message = format!("✓ No display — copied URL to clipboard: {}", url);
+            }
+            Err(e) => {
+                self.status_message = format!("✗ Could not open browser: {}", e);
+            }
+        }
+    }
+
+    /// Preview the selected post, starting the dev server first if nothing is serving the site
+    fn preview_selected(&mut self) {
+        let Some(post) = self.get_filtered_posts().get(self.selected).map(|p| (*p).clone()) else { return };
+        match self.server.as_ref().map(|s| s.status.clone()) {
+            Some(ServerStatus::Ready(_)) => self.open_preview(&post),
+            Some(ServerStatus::Starting) => {
+                self.pending_preview = Some(post.path);
+                self.status_message = "Dev server starting — preview opens when it is ready".to_string();
+            }
+            _ if is_listening(&self.site.dev_server_url(&self.config.ssg)) => self.open_preview(&post),
+            _ => {
+                self.start_server();
+                if self.server.as_ref().is_some_and(|s| s.is_running()) {
+                    self.pending_preview = Some(post.path);
+                    self.status_message = "Starting dev server — preview opens when it is ready".to_string();
+                }
+            }
+        }
+    }
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Highlight case-insensitive occurrences of `query` in a line.
+/// Returns the line and whether anything matched.
+fn highlight_query(line: Line<'static>, query: &str) -> (Line<'static>, bool) {
+    if query.is_empty() {
+        return (line, false);
+    }
+
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    let ranges: Vec<(usize, usize)> = haystack
+        .match_indices(&needle)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect();
+    if ranges.is_empty() {
+        return (line, false);
+    }
+
+    let match_style = Style::default().bg(Color::Yellow).fg(Color::Black);
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in line.spans {
+        let content = span.content.as_ref();
+        let end = offset + content.len();
+        let mut cut = offset;
+        for &(m_start, m_end) in &ranges {
+            let (s, e) = (m_start.max(offset), m_end.min(end));
+            if s >= e {
+                continue;
+            }
+            if s > cut {
+                spans.push(Span::styled(text[cut..s].to_string(), span.style));
+            }
+            spans.push(Span::styled(text[s..e].to_string(), span.style.patch(match_style)));
+            cut = e;
+        }
+        if cut < end {
+            spans.push(Span::styled(text[cut..end].to_string(), span.style));
+        }
+        offset = end;
+    }
+
+    (Line::from(spans).style(line.style), true)
+}
+
+/// Dev server status and the tail of its output
+fn render_server_panel(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let (status, color) = match app.server.as_ref().map(|s| &s.status) {
+        Some(ServerStatus::Starting) => ("starting…".to_string(), Color::Yellow),
+        Some(ServerStatus::Ready(url)) => (format!("ready at {}", url), Color::Green),
+        Some(ServerStatus::Crashed(Some(code))) => (format!("exited with code {}", code), Color::Red),
+        Some(ServerStatus::Crashed(None)) => ("killed by signal".to_string(), Color::Red),
+        Some(ServerStatus::Stopped) => ("stopped".to_string(), Color::DarkGray),
+        None => ("not running".to_string(), Color::DarkGray),
+    };
+    let command = app.server.as_ref().map(|s| format!(" — {}", s.command.display())).unwrap_or_default();
+    let title = Line::from(vec![
+        Span::raw("Server: "),
+        Span::styled(status, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(command, Style::default().fg(Color::DarkGray)),
+        Span::raw(" (S: start/stop | L: hide)"),
+    ]);
+
+    let height = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = app
+        .server
+        .as_ref()
+        .map(|server| {
+            let skip = server.log.len().saturating_sub(height);
+            server
+                .log
+                .iter()
+                .skip(skip)
+                .map(|(stream, line)| {
+                    let style = if is_error_line(line) {
+                        Style::default().fg(Color::Red)
+                    } else if *stream == Stream::Stderr {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::Gray)
+                    };
+                    Line::styled(line.clone(), style)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let panel = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(panel, area);
+}
+
+/// Build problems as a popup list over the panes
+fn render_problems(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let width = area.width.saturating_mul(4) / 5;
+    let height = area.height.saturating_mul(3) / 5;
+    let popup = ratatui::layout::Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let root = PathBuf::from(&app.config.site_path);
+    let visible = popup.height.saturating_sub(2) as usize;
+    let skip = (app.problems_selected + 1).saturating_sub(visible);
+    let lines: Vec<Line> = if app.problems.is_empty() {
+        vec![Line::styled("No problems reported", Style::default().fg(Color::DarkGray))]
+    } else {
+        app.problems
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(i, problem)| {
+                let color = match problem.severity {
+                    Severity::Error => Color::Red,
+                    Severity::Warning => Color::Yellow,
+                };
+                let mut spans = vec![Span::styled(format!("{} ", problem.icon()), Style::default().fg(color))];
+                if let Some(location) = problem.location(&root) {
+                    spans.push(Span::styled(format!("{}  ", location), Style::default().fg(Color::Cyan)));
+                }
+                spans.push(Span::raw(problem.message.clone()));
+                let line = Line::from(spans);
+                if i == app.problems_selected {
+                    line.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+                } else {
+                    line
+                }
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Problems: {} (j/k: move | Enter: jump | Esc: close)", app.problems_title))
+        .border_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD));
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
 }
 
 fn ui(f: &mut Frame, app: &App) {
-    // Main layout with status bar at bottom
+    // Main layout with status bar at bottom, and the server log above it when shown
+    let panel_height = if app.server_panel { 10 } else { 0 };
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .constraints([Constraint::Min(0), Constraint::Length(panel_height), Constraint::Length(1)])
         .split(f.area());
+    if app.server_panel {
+        render_server_panel(f, app, main_chunks[1]);
+    }
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -198,10 +820,10 @@ fn ui(f: &mut Frame, app: &App) {
     };
 
     let header = Row::new(vec![
-        Cell::from(title_header),
-        Cell::from(date_header),
-        Cell::from("Type"),
-        Cell::from("Status"),
+        TableCell::from(title_header),
+        TableCell::from(date_header),
+        TableCell::from("Type"),
+        TableCell::from("Status"),
     ])
     .style(Style::default().add_modifier(Modifier::BOLD));
 
@@ -227,10 +849,10 @@ fn ui(f: &mut Frame, app: &App) {
             };
 
             Row::new(vec![
-                Cell::from(post.title.as_str()),
-                Cell::from(date),
-                Cell::from(content_type),
-                Cell::from(status),
+                TableCell::from(post.title.as_str()),
+                TableCell::from(date),
+                TableCell::from(content_type),
+                TableCell::from(status),
             ])
             .style(style)
         })
@@ -328,7 +950,7 @@ fn ui(f: &mut Frame, app: &App) {
                     "draft" => Color::Yellow,
                     "content_type" | "type" => Color::Green,
                     "date" => Color::Blue,
-                    "tags" | "categories" => Color::Magenta,
+                    k if app.site.taxonomies.iter().any(|t| t == k) => Color::Magenta,
                     _ => Color::White,
                 };
 
@@ -394,19 +1016,51 @@ fn ui(f: &mut Frame, app: &App) {
     f.render_widget(metadata, right_chunks[0]);
 
     // Content pane
-    let content_text = if let Some(post) = selected_post {
-        let lines: Vec<&str> = post.content.lines().collect();
-        let visible_start = app.content_scroll;
-        let visible_end = (visible_start + 30).min(lines.len());
-        lines[visible_start..visible_end].join("\n")
+    // Reserve the rightmost column of the text area for the scrollbar
+    let content_area = right_chunks[1];
+    let text_area = content_area.inner(Margin { horizontal: 1, vertical: 1 });
+    let viewport_height = text_area.height as usize;
+    app.content_viewport.set((text_area.width.saturating_sub(1), text_area.height));
+
+    let mut editor_cursor = None;
+    let (content_rows, scroll) = if let Some((_, editor)) = &app.editor {
+        let (rows, (cursor_row, cursor_col)) = editor.render(text_area.width.saturating_sub(1) as usize);
+        // Keep the cursor visible even if the pane was resized since the last key
+        let scroll = editor.scroll.min(cursor_row).max((cursor_row + 1).saturating_sub(viewport_height));
+        if editor.mode() == EditorMode::Insert {
+            editor_cursor = Some((text_area.x + cursor_col as u16, text_area.y + (cursor_row - scroll) as u16));
+        }
+        (Some(rows), scroll)
     } else {
-        "No post selected".to_string()
+        let rows = app.content_layout().map(|l| l.rows);
+        let total = rows.as_ref().map(|r| r.len()).unwrap_or(0);
+        (rows, app.content_scroll.min(total.saturating_sub(viewport_height)))
     };
+    let total_rows = content_rows.as_ref().map(|r| r.len()).unwrap_or(0);
 
-    let content_title = if app.focused_pane == 2 {
-        "Content [FOCUSED]"
+    let content_text: Vec<Line> = match content_rows {
+        Some(rows) => rows.into_iter().skip(scroll).take(viewport_height).collect(),
+        None => vec![Line::from("No post selected")],
+    };
+
+    let content_title = if let Some((_, editor)) = &app.editor {
+        let (line, col) = editor.position();
+        let dirty = if editor.is_dirty() { " [+]" } else { "" };
+        format!("Content [EDIT: {}] {}:{}{}", editor.mode().label(), line, col, dirty)
     } else {
-        "Content"
+        let view = if app.content_view == ContentView::Raw { " [RAW]" } else { "" };
+        let find = if app.content_search_mode || !app.content_query.is_empty() {
+            format!(" [FIND: \"{}\"]", app.content_query)
+        } else {
+            String::new()
+        };
+        let position = if total_rows > viewport_height {
+            format!(" {}%", (scroll + viewport_height).min(total_rows) * 100 / total_rows)
+        } else {
+            String::new()
+        };
+        let focus = if app.focused_pane == 2 { " [FOCUSED]" } else { "" };
+        format!("Content{}{}{}{}", view, find, position, focus)
     };
 
     let content_block = Block::default()
@@ -419,21 +1073,44 @@ fn ui(f: &mut Frame, app: &App) {
         });
 
     let content = Paragraph::new(content_text).block(content_block);
-    f.render_widget(content, right_chunks[1]);
+    f.render_widget(content, content_area);
+
+    if total_rows > viewport_height {
+        let mut scrollbar_state = ScrollbarState::new(total_rows.saturating_sub(viewport_height))
+            .position(scroll)
+            .viewport_content_length(viewport_height);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None);
+        f.render_stateful_widget(scrollbar, text_area, &mut scrollbar_state);
+    }
+    if let Some((x, y)) = editor_cursor {
+        f.set_cursor_position((x, y));
+    }
 
     // Status bar
     let status_text = if !app.status_message.is_empty() {
         app.status_message.clone()
+    } else if app.editor.is_some() {
+        "Esc/q: leave editor | i/a/o: insert | v/V: select | d/c/y + motion | p: paste | u: undo | Ctrl+S: save".to_string()
+    } else if app.content_search_mode {
+        "Find in content - Type to search | Enter: keep | Esc: clear".to_string()
     } else if app.search_mode {
         format!("Search mode - Type to filter | Enter/Esc: exit search | {} matches", app.get_filtered_posts().len())
     } else if app.focused_pane == 1 {
-        "q: quit | j/k: navigate | Enter: edit/add | d: delete field | Ctrl+S: save | Tab: switch panes | s: sort | f: filter | /: search | o: preview | r: refresh".to_string()
+        "q: quit | j/k: navigate | Enter: edit/add | d: delete field | Ctrl+S: save | Tab: switch panes | s: sort | f: filter | /: search | o: preview | S: server | L: log | B: build | P: problems | r: refresh".to_string()
+    } else if app.focused_pane == 2 {
+        "q: quit | j/k: scroll | PgUp/PgDn: page | g/G: top/bottom | [/]: headings | /: find | n/N: next/prev match | v: rendered/raw | e: edit | Enter: $EDITOR | Tab/h/l: switch panes".to_string()
     } else {
-        "q: quit | j/k: navigate | Tab/h/l: switch panes | Enter: edit (meta) or open editor (content) | Ctrl+S: save | s: sort | f: filter | /: search | o: preview | r: refresh".to_string()
+        "q: quit | j/k: navigate | Tab/h/l: switch panes | Enter: edit (meta) or open editor (content) | Ctrl+S: save | s: sort | f: filter | /: search | o: preview | S: server | L: log | B: build | P: problems | r: refresh".to_string()
     };
     let status_bar = Paragraph::new(status_text)
         .style(Style::default().fg(Color::Gray));
-    f.render_widget(status_bar, main_chunks[1]);
+    f.render_widget(status_bar, main_chunks[2]);
+
+    if app.problems_open {
+        render_problems(f, app);
+    }
 }
 
 pub async fn run() -> Result<()> {
@@ -449,24 +1126,40 @@ pub async fn run() -> Result<()> {
 
     // Main loop
     loop {
+        app.poll_server();
+        app.poll_build();
         terminal.draw(|f| ui(f, &app))?;
 
+        // Poll so files open in detached editors and server or build output are picked up while idle
+        let running = app.server.as_ref().is_some_and(|s| s.is_running()) || app.build.is_some();
+        if !event::poll(Duration::from_millis(if running { 100 } else { 500 }))? {
+            app.check_watched();
+            continue;
+        }
+
         if let Event::Key(key) = event::read()? {
             // Clear status message on any key press (except when saving)
             if !key.modifiers.contains(KeyModifiers::CONTROL) || key.code != KeyCode::Char('s') {
                 app.status_message.clear();
             }
 
+            // Built-in body editor captures all keys while open
+            if app.editor.is_some() {
+                app.handle_editor_key(key);
+            }
+            else if app.problems_open {
+                app.handle_problems_key(key);
+            }
             // Handle search mode input
-            if app.search_mode {
+            else if app.search_mode {
                 match key.code {
                     KeyCode::Char(c) => {
                         app.search_query.push(c);
-                        app.selected = 0; // Reset selection when search changes
+                        app.select(0); // Reset selection when search changes
                     }
                     KeyCode::Backspace => {
                         app.search_query.pop();
-                        app.selected = 0;
+                        app.select(0);
                     }
                     KeyCode::Esc | KeyCode::Enter => {
                         // Exit search mode
@@ -475,6 +1168,25 @@ pub async fn run() -> Result<()> {
                     _ => {}
                 }
             }
+            // Handle in-content search input
+            else if app.content_search_mode {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        app.content_query.push(c);
+                        app.jump_match(true, true);
+                    }
+                    KeyCode::Backspace => {
+                        app.content_query.pop();
+                        app.jump_match(true, true);
+                    }
+                    KeyCode::Enter => app.content_search_mode = false,
+                    KeyCode::Esc => {
+                        app.content_search_mode = false;
+                        app.content_query.clear();
+                    }
+                    _ => {}
+                }
+            }
             // Handle edit mode input (including adding new fields)
             else if app.edit_mode || app.adding_field {
                 match key.code {
@@ -612,28 +1324,18 @@ pub async fn run() -> Result<()> {
                                     }
                                 }
                             }
-                            2 => {
-                                // Content pane - scroll down
-                                app.content_scroll += 1;
-                            }
+                            2 => app.scroll_content_by(1), // Content pane - scroll down
                             _ => {}
                         }
                     }
                     KeyCode::Char('k') | KeyCode::Up => {
                         match app.focused_pane {
                             0 => app.select_prev(), // Posts pane
-                            1 => {
+                            1 if app.metadata_selected > 0 => {
                                 // Metadata pane - navigate fields
-                                if app.metadata_selected > 0 {
-                                    app.metadata_selected -= 1;
-                                }
-                            }
-                            2 => {
-                                // Content pane - scroll up
-                                if app.content_scroll > 0 {
-                                    app.content_scroll -= 1;
-                                }
+                                app.metadata_selected -= 1;
                             }
+                            2 => app.scroll_content_by(-1), // Content pane - scroll up
                             _ => {}
                         }
                     }
@@ -687,12 +1389,18 @@ pub async fn run() -> Result<()> {
                             }
                         } else if app.focused_pane == 2 {
                             // Open in external editor if in content pane
-                            if let Err(e) = app.open_in_editor() {
-                                app.status_message = format!("✗ Error opening editor: {}", e);
-                            } else {
-                                // Reload posts after editing
-                                app.posts = scan_posts(&app.config)?;
-                                app.status_message = "✓ Reloaded after edit".to_string();
+                            match app.open_in_editor() {
+                                Ok(EditorLaunch::Finished) => {
+                                    // Reload posts after editing
+                                    app.posts = scan_posts(&app.config)?;
+                                    app.status_message = "✓ Reloaded after edit".to_string();
+                                }
+                                Ok(EditorLaunch::Detached) => {
+                                    app.status_message = "✓ Opened in editor — changes reload automatically".to_string();
+                                }
+                                Err(e) => {
+                                    app.status_message = format!("✗ Error opening editor: {}", e);
+                                }
                             }
                             // Redraw after returning from editor
                             terminal.clear()?;
@@ -708,76 +1416,91 @@ pub async fn run() -> Result<()> {
                         app.metadata_selected = 0;
                         app.content_scroll = 0;
                     }
-                    KeyCode::Char('d') => {
+                    KeyCode::Char('d') if app.focused_pane == 1 => {
                         // Delete metadata field when in metadata pane
-                        if app.focused_pane == 1 {
-                            let post_path = {
-                                let filtered = app.get_filtered_posts();
-                                filtered.get(app.selected).map(|p| p.path.clone())
-                            };
+                        let post_path = {
+                            let filtered = app.get_filtered_posts();
+                            filtered.get(app.selected).map(|p| p.path.clone())
+                        };
 
-                            if let Some(path) = post_path {
-                                if let Some(actual_post) = app.posts.iter_mut().find(|p| p.path == path) {
-                                    let mut keys: Vec<String> = actual_post.frontmatter.keys().cloned().collect();
-                                    keys.sort();
+                        if let Some(path) = post_path {
+                            if let Some(actual_post) = app.posts.iter_mut().find(|p| p.path == path) {
+                                let mut keys: Vec<String> = actual_post.frontmatter.keys().cloned().collect();
+                                keys.sort();
 
-                                    // Don't allow deleting if on "Add field" row
-                                    if app.metadata_selected < keys.len() {
-                                        if let Some(key) = keys.get(app.metadata_selected) {
-                                            // Don't allow deleting critical fields
-                                            if key != "title" {
-                                                actual_post.frontmatter.remove(key);
-                                                app.status_message = format!("✓ Deleted field: {}", key);
-                                                // Move selection up if we were at the last field
-                                                if app.metadata_selected > 0 && app.metadata_selected >= actual_post.frontmatter.len() {
-                                                    app.metadata_selected -= 1;
-                                                }
-                                            } else {
-                                                app.status_message = "✗ Cannot delete title field".to_string();
+                                // Don't allow deleting if on "Add field" row
+                                if app.metadata_selected < keys.len() {
+                                    if let Some(key) = keys.get(app.metadata_selected) {
+                                        // Don't allow deleting critical fields
+                                        if key != "title" {
+                                            actual_post.frontmatter.remove(key);
+                                            app.status_message = format!("✓ Deleted field: {}", key);
+                                            // Move selection up if we were at the last field
+                                            if app.metadata_selected > 0 && app.metadata_selected >= actual_post.frontmatter.len() {
+                                                app.metadata_selected -= 1;
                                             }
+                                        } else {
+                                            app.status_message = "✗ Cannot delete title field".to_string();
                                         }
                                     }
                                 }
                             }
                         }
                     }
+                    KeyCode::PageDown | KeyCode::Char(' ') if app.focused_pane == 2 => {
+                        app.scroll_content_by(app.content_height() as isize);
+                    }
+                    KeyCode::PageUp if app.focused_pane == 2 => {
+                        app.scroll_content_by(-(app.content_height() as isize));
+                    }
+                    KeyCode::Char('f') if app.focused_pane == 2 && key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.scroll_content_by(app.content_height() as isize);
+                    }
+                    KeyCode::Char('b') if app.focused_pane == 2 && key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.scroll_content_by(-(app.content_height() as isize));
+                    }
+                    KeyCode::Char('g') | KeyCode::Home if app.focused_pane == 2 => app.scroll_content_to(0),
+                    KeyCode::Char('G') | KeyCode::End if app.focused_pane == 2 => app.scroll_content_to(usize::MAX),
+                    KeyCode::Char(']') if app.focused_pane == 2 => app.jump_heading(true),
+                    KeyCode::Char('[') if app.focused_pane == 2 => app.jump_heading(false),
+                    KeyCode::Char('/') if app.focused_pane == 2 => {
+                        // Find within the selected post
+                        app.content_search_mode = true;
+                        app.content_query.clear();
+                        app.content_match = 0;
+                    }
+                    KeyCode::Char('e') if app.focused_pane == 2 => app.open_body_editor(),
+                    KeyCode::Char('n') if app.focused_pane == 2 => app.jump_match(true, false),
+                    KeyCode::Char('N') if app.focused_pane == 2 => app.jump_match(false, false),
                     KeyCode::Char('s') => app.cycle_sort(),
                     KeyCode::Char('f') => app.toggle_drafts(),
+                    KeyCode::Char('v') => app.toggle_content_view(),
                     KeyCode::Char('r') => {
                         app.posts = scan_posts(&app.config)?;
+                        app.site = app.config.site_config();
                     }
-                    KeyCode::Char('o') => {
-                        // Open current post in browser
-                        let filtered = app.get_filtered_posts();
-                        if let Some(post) = filtered.get(app.selected) {
-                            if let Some(url) = app.config.preview_url(&post.path) {
-                                match Command::new("open").arg(&url).spawn() {
-                                    Ok(_) => {
-                                        app.status_message = format!("✓ Opening in browser: {}", url);
-                                    }
-                                    Err(e) => {
-                                        app.status_message = format!("✗ Could not open browser: {}", e);
-                                    }
-                                }
-                            } else {
-                                app.status_message = "✗ Could not construct preview URL".to_string();
-                            }
-                        }
-                    }
+                    KeyCode::Char('o') => app.preview_selected(),
+                    KeyCode::Char('S') => app.toggle_server(),
+                    KeyCode::Char('L') => app.server_panel = !app.server_panel,
+                    KeyCode::Char('B') => app.start_build(),
+                    KeyCode::Char('P') => app.problems_open = true,
                     KeyCode::Char('/') => {
                         // Enter search mode
                         app.search_mode = true;
                         app.search_query.clear();
-                        app.selected = 0;
+                        app.select(0);
                         app.status_message = "Search mode: type to filter posts".to_string();
                     }
-                    KeyCode::Esc => {
+                    KeyCode::Esc if app.focused_pane == 2 && !app.content_query.is_empty() => {
+                        // Clear in-content search
+                        app.content_query.clear();
+                        app.status_message = "Find cleared".to_string();
+                    }
+                    KeyCode::Esc if !app.search_query.is_empty() => {
                         // Clear search if active
-                        if !app.search_query.is_empty() {
-                            app.search_query.clear();
-                            app.selected = 0;
-                            app.status_message = "Search cleared".to_string();
-                        }
+                        app.search_query.clear();
+                        app.select(0);
+                        app.status_message = "Search cleared".to_string();
                     }
                     _ => {}
                 }
@@ -0,0 +1,656 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthChar;
+
+/// A position in the buffer: (line, char column)
+type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Normal,
+    Insert,
+    Visual,
+    VisualLine,
+}
+
+impl Mode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Mode::Normal => "NORMAL",
+            Mode::Insert => "INSERT",
+            Mode::Visual => "VISUAL",
+            Mode::VisualLine => "VISUAL LINE",
+        }
+    }
+}
+
+/// What the caller should do after a key was handled
+#[derive(Debug, PartialEq)]
+pub enum EditorAction {
+    None,
+    Save,
+    Exit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CharClass {
+    Space,
+    Word,
+    Punct,
+}
+
+fn class(c: char) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::Space
+    } else if c.is_alphanumeric() || c == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punct
+    }
+}
+
+/// Multi-line text editor with vim-style modal editing
+pub struct Editor {
+    lines: Vec<String>,
+    cursor: Pos,
+    anchor: Pos, // Selection start in visual modes
+    mode: Mode,
+    pending: Option<char>, // Operator or `g` prefix awaiting a motion
+    register: String,
+    register_linewise: bool,
+    undo: Vec<(Vec<String>, Pos)>,
+    original: String,
+    pub scroll: usize, // First visible display row
+}
+
+impl Editor {
+    pub fn new(text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(String::from).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Self {
+            lines,
+            cursor: (0, 0),
+            anchor: (0, 0),
+            mode: Mode::Normal,
+            pending: None,
+            register: String::new(),
+            register_linewise: false,
+            undo: Vec::new(),
+            original: text.to_string(),
+            scroll: 0,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Whether the buffer differs from the text it was opened with
+    pub fn is_dirty(&self) -> bool {
+        self.text() != self.original.trim_end_matches('\n')
+    }
+
+    /// Treat the current buffer as the saved state
+    pub fn mark_saved(&mut self) {
+        self.original = self.text();
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Line and column of the cursor, 1-based, for status display
+    pub fn position(&self) -> (usize, usize) {
+        (self.cursor.0 + 1, self.cursor.1 + 1)
+    }
+
+    // --- Buffer primitives ---
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    fn byte_idx(&self, row: usize, col: usize) -> usize {
+        self.lines[row]
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(self.lines[row].len())
+    }
+
+    /// Character at a position; the end of a non-final line reads as a newline
+    fn char_at(&self, (row, col): Pos) -> Option<char> {
+        match self.lines[row].chars().nth(col) {
+            Some(c) => Some(c),
+            None if row + 1 < self.lines.len() => Some('\n'),
+            None => None,
+        }
+    }
+
+    fn next_pos(&self, (row, col): Pos) -> Option<Pos> {
+        if col < self.line_len(row) {
+            Some((row, col + 1))
+        } else if row + 1 < self.lines.len() {
+            Some((row + 1, 0))
+        } else {
+            None
+        }
+    }
+
+    fn prev_pos(&self, (row, col): Pos) -> Option<Pos> {
+        if col > 0 {
+            Some((row, col - 1))
+        } else if row > 0 {
+            Some((row - 1, self.line_len(row - 1)))
+        } else {
+            None
+        }
+    }
+
+    fn snapshot(&mut self) {
+        self.undo.push((self.lines.clone(), self.cursor));
+        if self.undo.len() > 200 {
+            self.undo.remove(0);
+        }
+    }
+
+    fn text_range(&self, start: Pos, end: Pos) -> String {
+        if start.0 == end.0 {
+            let line = &self.lines[start.0];
+            return line[self.byte_idx(start.0, start.1)..self.byte_idx(end.0, end.1)].to_string();
+        }
+        let mut out = self.lines[start.0][self.byte_idx(start.0, start.1)..].to_string();
+        for row in start.0 + 1..end.0 {
+            out.push('\n');
+            out.push_str(&self.lines[row]);
+        }
+        out.push('\n');
+        out.push_str(&self.lines[end.0][..self.byte_idx(end.0, end.1)]);
+        out
+    }
+
+    /// Delete the text in [start, end) and return it
+    fn delete_range(&mut self, start: Pos, end: Pos) -> String {
+        let removed = self.text_range(start, end);
+        let prefix = self.lines[start.0][..self.byte_idx(start.0, start.1)].to_string();
+        let suffix = self.lines[end.0][self.byte_idx(end.0, end.1)..].to_string();
+        self.lines.splice(start.0..=end.0, [format!("{}{}", prefix, suffix)]);
+        self.cursor = start;
+        removed
+    }
+
+    /// Insert text at a position, leaving the cursor after it
+    fn insert_text(&mut self, (row, col): Pos, text: &str) {
+        let at = self.byte_idx(row, col);
+        let suffix = self.lines[row].split_off(at);
+        let mut parts = text.split('\n');
+        self.lines[row].push_str(parts.next().unwrap_or(""));
+        let mut cursor_row = row;
+        for part in parts {
+            cursor_row += 1;
+            self.lines.insert(cursor_row, part.to_string());
+        }
+        let cursor_col = self.line_len(cursor_row);
+        self.lines[cursor_row].push_str(&suffix);
+        self.cursor = (cursor_row, cursor_col);
+    }
+
+    fn delete_lines(&mut self, first: usize, last: usize) -> String {
+        let removed = self.lines.drain(first..=last).collect::<Vec<_>>().join("\n");
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.cursor = (first.min(self.lines.len() - 1), 0);
+        removed
+    }
+
+    fn clamp_cursor(&mut self) {
+        self.cursor.0 = self.cursor.0.min(self.lines.len() - 1);
+        let len = self.line_len(self.cursor.0);
+        let max = if self.mode == Mode::Insert { len } else { len.saturating_sub(1) };
+        self.cursor.1 = self.cursor.1.min(max);
+    }
+
+    // --- Motions ---
+
+    fn word_forward(&self, mut pos: Pos) -> Pos {
+        if let Some(c) = self.char_at(pos) {
+            let start = class(c);
+            if start != CharClass::Space {
+                while let Some(next) = self.next_pos(pos) {
+                    pos = next;
+                    if self.char_at(pos).map(class) != Some(start) {
+                        break;
+                    }
+                }
+            }
+        }
+        while self.char_at(pos).map(class) == Some(CharClass::Space) {
+            match self.next_pos(pos) {
+                Some(next) => pos = next,
+                None => break,
+            }
+        }
+        pos
+    }
+
+    fn word_end(&self, mut pos: Pos) -> Pos {
+        pos = self.next_pos(pos).unwrap_or(pos);
+        while self.char_at(pos).map(class) == Some(CharClass::Space) {
+            match self.next_pos(pos) {
+                Some(next) => pos = next,
+                None => return pos,
+            }
+        }
+        let Some(c) = self.char_at(pos) else { return pos };
+        while let Some(next) = self.next_pos(pos) {
+            if self.char_at(next).map(class) != Some(class(c)) {
+                break;
+            }
+            pos = next;
+        }
+        pos
+    }
+
+    fn word_back(&self, mut pos: Pos) -> Pos {
+        pos = self.prev_pos(pos).unwrap_or(pos);
+        while self.char_at(pos).map(class) == Some(CharClass::Space) {
+            match self.prev_pos(pos) {
+                Some(prev) => pos = prev,
+                None => return pos,
+            }
+        }
+        let Some(c) = self.char_at(pos) else { return pos };
+        while let Some(prev) = self.prev_pos(pos) {
+            if self.char_at(prev).map(class) != Some(class(c)) {
+                break;
+            }
+            pos = prev;
+        }
+        pos
+    }
+
+    fn first_non_blank(&self, row: usize) -> usize {
+        self.lines[row].chars().take_while(|c| c.is_whitespace()).count()
+    }
+
+    /// Target of a motion key, and whether the motion includes the target char
+    fn motion(&self, key: KeyCode) -> Option<(Pos, bool)> {
+        let (row, col) = self.cursor;
+        let last_row = self.lines.len() - 1;
+        let target = match key {
+            KeyCode::Char('h') | KeyCode::Left => ((row, col.saturating_sub(1)), false),
+            KeyCode::Char('l') | KeyCode::Right => ((row, (col + 1).min(self.line_len(row))), false),
+            KeyCode::Char('j') | KeyCode::Down => ((((row + 1).min(last_row)), col), false),
+            KeyCode::Char('k') | KeyCode::Up => ((row.saturating_sub(1), col), false),
+            KeyCode::Char('w') => (self.word_forward(self.cursor), false),
+            KeyCode::Char('e') => (self.word_end(self.cursor), true),
+            KeyCode::Char('b') => (self.word_back(self.cursor), false),
+            KeyCode::Char('0') | KeyCode::Home => ((row, 0), false),
+            KeyCode::Char('^') => ((row, self.first_non_blank(row)), false),
+            KeyCode::Char('$') | KeyCode::End => ((row, self.line_len(row)), false),
+            KeyCode::Char('G') => ((last_row, self.first_non_blank(last_row)), false),
+            KeyCode::Char('}') => {
+                let next = (row + 1..=last_row)
+                    .find(|&r| self.lines[r].trim().is_empty())
+                    .unwrap_or(last_row);
+                ((next, 0), false)
+            }
+            KeyCode::Char('{') => {
+                let prev = (0..row).rev().find(|&r| self.lines[r].trim().is_empty()).unwrap_or(0);
+                ((prev, 0), false)
+            }
+            _ => return None,
+        };
+        Some(target)
+    }
+
+    // --- Operators ---
+
+    fn selection(&self) -> (Pos, Pos) {
+        if self.anchor <= self.cursor {
+            (self.anchor, self.cursor)
+        } else {
+            (self.cursor, self.anchor)
+        }
+    }
+
+    /// Apply `d`, `c` or `y` to a char-wise range [start, end)
+    fn operate(&mut self, op: char, start: Pos, end: Pos) {
+        if op == 'y' {
+            self.register = self.text_range(start, end);
+            self.register_linewise = false;
+            self.cursor = start;
+            return;
+        }
+        self.snapshot();
+        self.register = self.delete_range(start, end);
+        self.register_linewise = false;
+        if op == 'c' {
+            self.mode = Mode::Insert;
+        }
+    }
+
+    /// Apply `d`, `c` or `y` to whole lines
+    fn operate_lines(&mut self, op: char, first: usize, last: usize) {
+        if op == 'y' {
+            self.register = self.lines[first..=last].join("\n");
+            self.register_linewise = true;
+            self.cursor = (first, self.cursor.1);
+            return;
+        }
+        self.snapshot();
+        if op == 'c' {
+            self.register = self.lines[first..=last].join("\n");
+            self.lines.splice(first..=last, [String::new()]);
+            self.cursor = (first, 0);
+            self.mode = Mode::Insert;
+        } else {
+            self.register = self.delete_lines(first, last);
+        }
+        self.register_linewise = true;
+    }
+
+    fn paste(&mut self, after: bool) {
+        if self.register.is_empty() {
+            return;
+        }
+        self.snapshot();
+        let text = self.register.clone();
+        if self.register_linewise {
+            let row = if after { self.cursor.0 + 1 } else { self.cursor.0 };
+            for (i, line) in text.split('\n').enumerate() {
+                self.lines.insert(row + i, line.to_string());
+            }
+            self.cursor = (row, 0);
+        } else {
+            let col = if after && self.line_len(self.cursor.0) > 0 { self.cursor.1 + 1 } else { self.cursor.1 };
+            self.insert_text((self.cursor.0, col), &text);
+            self.cursor.1 = self.cursor.1.saturating_sub(1);
+        }
+    }
+
+    // --- Key handling ---
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> EditorAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl && key.code == KeyCode::Char('s') {
+            return EditorAction::Save;
+        }
+
+        match self.mode {
+            Mode::Insert => self.insert_key(key),
+            Mode::Normal => return self.normal_key(key),
+            Mode::Visual | Mode::VisualLine => self.visual_key(key),
+        }
+        self.clamp_cursor();
+        EditorAction::None
+    }
+
+    fn insert_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.cursor.1 = self.cursor.1.saturating_sub(1);
+            }
+            KeyCode::Char(c) => {
+                self.insert_text(self.cursor, &c.to_string());
+            }
+            KeyCode::Tab => {
+                self.insert_text(self.cursor, "    ");
+            }
+            KeyCode::Enter => {
+                self.insert_text(self.cursor, "\n");
+            }
+            KeyCode::Backspace => {
+                if let Some(prev) = self.prev_pos(self.cursor) {
+                    self.delete_range(prev, self.cursor);
+                    }
+            }
+            KeyCode::Delete => {
+                if let Some(next) = self.next_pos(self.cursor) {
+                    self.delete_range(self.cursor, next);
+                    }
+            }
+            code => {
+                if let Some((pos, _)) = self.motion(code) {
+                    self.cursor = pos;
+                }
+            }
+        }
+    }
+
+    fn normal_key(&mut self, key: KeyEvent) -> EditorAction {
+        let (row, col) = self.cursor;
+
+        // Complete a pending `g` prefix or operator
+        if let Some(pending) = self.pending.take() {
+            match (pending, key.code) {
+                ('g', KeyCode::Char('g')) => self.cursor = (0, self.first_non_blank(0)),
+                (op, KeyCode::Char(c)) if c == op => self.operate_lines(op, row, row),
+                (op, KeyCode::Char('j')) if op != 'g' => {
+                    self.operate_lines(op, row, (row + 1).min(self.lines.len() - 1))
+                }
+                (op, KeyCode::Char('k')) if op != 'g' => self.operate_lines(op, row.saturating_sub(1), row),
+                (op, KeyCode::Char('G')) if op != 'g' => self.operate_lines(op, row, self.lines.len() - 1),
+                (op, code) if op != 'g' => {
+                    // `cw` behaves like `ce`, as in vim
+                    let code = if op == 'c' && code == KeyCode::Char('w') { KeyCode::Char('e') } else { code };
+                    if let Some((target, inclusive)) = self.motion(code) {
+                        let end = if inclusive { self.next_pos(target).unwrap_or(target) } else { target };
+                        if end >= self.cursor {
+                            self.operate(op, self.cursor, end);
+                        } else {
+                            self.operate(op, end, self.cursor);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            self.clamp_cursor();
+            return EditorAction::None;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return EditorAction::Exit,
+            KeyCode::Char('i') => {
+                self.snapshot();
+                self.mode = Mode::Insert;
+            }
+            KeyCode::Char('a') => {
+                self.snapshot();
+                self.mode = Mode::Insert;
+                self.cursor.1 = (col + 1).min(self.line_len(row));
+            }
+            KeyCode::Char('I') => {
+                self.snapshot();
+                self.mode = Mode::Insert;
+                self.cursor.1 = self.first_non_blank(row);
+            }
+            KeyCode::Char('A') => {
+                self.snapshot();
+                self.mode = Mode::Insert;
+                self.cursor.1 = self.line_len(row);
+            }
+            KeyCode::Char('o') => {
+                self.snapshot();
+                self.lines.insert(row + 1, String::new());
+                self.cursor = (row + 1, 0);
+                self.mode = Mode::Insert;
+            }
+            KeyCode::Char('O') => {
+                self.snapshot();
+                self.lines.insert(row, String::new());
+                self.cursor = (row, 0);
+                self.mode = Mode::Insert;
+            }
+            KeyCode::Char('v') => {
+                self.mode = Mode::Visual;
+                self.anchor = self.cursor;
+            }
+            KeyCode::Char('V') => {
+                self.mode = Mode::VisualLine;
+                self.anchor = self.cursor;
+            }
+            KeyCode::Char('x') | KeyCode::Delete if self.line_len(row) > 0 => {
+                self.operate('d', (row, col), (row, col + 1));
+            }
+            KeyCode::Char('D') => self.operate('d', (row, col), (row, self.line_len(row))),
+            KeyCode::Char('C') => self.operate('c', (row, col), (row, self.line_len(row))),
+            KeyCode::Char('J') if row + 1 < self.lines.len() => {
+                self.snapshot();
+                let next = self.lines.remove(row + 1);
+                let joined = next.trim_start();
+                self.cursor.1 = self.line_len(row);
+                if !joined.is_empty() {
+                    self.lines[row].push(' ');
+                    self.lines[row].push_str(joined);
+                }
+            }
+            KeyCode::Char('p') => self.paste(true),
+            KeyCode::Char('P') => self.paste(false),
+            KeyCode::Char('u') => {
+                if let Some((lines, cursor)) = self.undo.pop() {
+                    self.lines = lines;
+                    self.cursor = cursor;
+                }
+            }
+            KeyCode::Char(c @ ('d' | 'c' | 'y' | 'g')) => self.pending = Some(c),
+            code => {
+                if let Some((pos, _)) = self.motion(code) {
+                    self.cursor = pos;
+                }
+            }
+        }
+        self.clamp_cursor();
+        EditorAction::None
+    }
+
+    fn visual_key(&mut self, key: KeyEvent) {
+        let linewise = self.mode == Mode::VisualLine;
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Char('v') if !linewise => self.mode = Mode::Normal,
+            KeyCode::Char('V') if linewise => self.mode = Mode::Normal,
+            KeyCode::Char('v') => self.mode = Mode::Visual,
+            KeyCode::Char('V') => self.mode = Mode::VisualLine,
+            KeyCode::Char('o') => std::mem::swap(&mut self.anchor, &mut self.cursor),
+            KeyCode::Char(c @ ('d' | 'x' | 'c' | 'y')) => {
+                let op = if c == 'x' { 'd' } else { c };
+                let (start, end) = self.selection();
+                self.mode = Mode::Normal;
+                if linewise {
+                    self.operate_lines(op, start.0, end.0);
+                } else {
+                    // Visual selections include the character under the cursor
+                    let end = self.next_pos(end).unwrap_or((end.0, self.line_len(end.0)));
+                    self.operate(op, start, end);
+                }
+            }
+            KeyCode::Char('g') => self.cursor = (0, 0),
+            code => {
+                if let Some((pos, _)) = self.motion(code) {
+                    self.cursor = pos;
+                }
+            }
+        }
+    }
+
+    // --- Rendering ---
+
+    /// Char offsets where each soft-wrapped segment of a line starts
+    fn wrap_points(line: &str, width: usize) -> Vec<usize> {
+        let mut points = vec![0];
+        let mut row_width = 0;
+        let mut last_space: Option<usize> = None;
+        for (i, c) in line.chars().enumerate() {
+            let w = c.width().unwrap_or(0);
+            if row_width + w > width {
+                let start = *points.last().unwrap();
+                // Break after the last space in this segment, or mid-word if there is none
+                let point = match last_space {
+                    Some(s) if s >= start && s < i => s + 1,
+                    _ => i,
+                };
+                points.push(point);
+                row_width = line.chars().skip(point).take(i - point).filter_map(|c| c.width()).sum();
+                last_space = None;
+            }
+            if c.is_whitespace() {
+                last_space = Some(i);
+            }
+            row_width += w;
+        }
+        points
+    }
+
+    /// Display rows for the buffer at `width`, with the cursor's (row, column) on screen
+    pub fn render(&self, width: usize) -> (Vec<Line<'static>>, (usize, usize)) {
+        let width = width.max(1);
+        let selection = match self.mode {
+            Mode::Visual => Some(self.selection()),
+            Mode::VisualLine => {
+                let (start, end) = self.selection();
+                Some(((start.0, 0), (end.0, usize::MAX)))
+            }
+            _ => None,
+        };
+        let selected_style = Style::default().add_modifier(Modifier::REVERSED);
+        let cursor_style = Style::default().bg(Color::DarkGray);
+
+        let mut rows = Vec::new();
+        let mut cursor_screen = (0, 0);
+        for (r, line) in self.lines.iter().enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            let points = Self::wrap_points(line, width);
+            for (seg, &start) in points.iter().enumerate() {
+                let end = points.get(seg + 1).copied().unwrap_or(chars.len());
+                let on_cursor_segment = r == self.cursor.0
+                    && self.cursor.1 >= start
+                    && (self.cursor.1 < end || seg + 1 == points.len());
+                if on_cursor_segment {
+                    let col: usize = chars[start..self.cursor.1.min(chars.len())]
+                        .iter()
+                        .filter_map(|c| c.width())
+                        .sum();
+                    cursor_screen = (rows.len(), col);
+                }
+
+                let spans: Vec<Span<'static>> = chars[start..end]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        let pos = (r, start + i);
+                        let in_selection = selection.is_some_and(|(s, e)| pos >= s && pos <= e);
+                        let style = if in_selection {
+                            selected_style
+                        } else if pos == self.cursor && self.mode != Mode::Insert {
+                            cursor_style
+                        } else {
+                            Style::default()
+                        };
+                        Span::styled(c.to_string(), style)
+                    })
+                    .collect();
+                rows.push(Line::from(spans));
+            }
+        }
+        (rows, cursor_screen)
+    }
+
+    /// Adjust `scroll` so the cursor row is within a viewport of `height` rows
+    pub fn scroll_into_view(&mut self, width: usize, height: usize) {
+        let (_, (cursor_row, _)) = self.render(width);
+        let height = height.max(1);
+        if cursor_row < self.scroll {
+            self.scroll = cursor_row;
+        } else if cursor_row >= self.scroll + height {
+            self.scroll = cursor_row + 1 - height;
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+/// Token-level syntax rules for a language family
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comment: Option<&'static str>,
+    quotes: &'static [char],
+}
+
+const RUST: Syntax = Syntax {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "else", "enum", "fn",
+        "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+        "return", "self", "Self", "static", "struct", "super", "trait", "true", "false", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    line_comment: Some("//"),
+    quotes: &['"'],
+};
+
+const JS: Syntax = Syntax {
+    keywords: &[
+        "async", "await", "break", "case", "catch", "class", "const", "continue", "default",
+        "else", "export", "extends", "false", "for", "from", "function", "if", "import", "in",
+        "instanceof", "interface", "let", "new", "null", "of", "return", "switch", "this",
+        "throw", "true", "try", "type", "typeof", "undefined", "var", "while",
+    ],
+    line_comment: Some("//"),
+    quotes: &['"', '\'', '`'],
+};
+
+const GO: Syntax = Syntax {
+    keywords: &[
+        "break", "case", "chan", "const", "continue", "default", "defer", "else", "false",
+        "for", "func", "go", "if", "import", "interface", "map", "nil", "package", "range",
+        "return", "select", "struct", "switch", "true", "type", "var",
+    ],
+    line_comment: Some("//"),
+    quotes: &['"', '`'],
+};
+
+const C_LIKE: Syntax = Syntax {
+    keywords: &[
+        "break", "case", "class", "const", "continue", "default", "do", "else", "enum",
+        "false", "for", "func", "if", "import", "let", "new", "nil", "null", "private",
+        "public", "return", "static", "struct", "switch", "this", "true", "var", "void", "while",
+    ],
+    line_comment: Some("//"),
+    quotes: &['"', '\''],
+};
+
+const PYTHON: Syntax = Syntax {
+    keywords: &[
+        "and", "as", "async", "await", "break", "class", "continue", "def", "elif", "else",
+        "except", "False", "finally", "for", "from", "if", "import", "in", "is", "lambda",
+        "None", "not", "or", "pass", "raise", "return", "True", "try", "while", "with", "yield",
+    ],
+    line_comment: Some("#"),
+    quotes: &['"', '\''],
+};
+
+const RUBY: Syntax = Syntax {
+    keywords: &[
+        "begin", "class", "def", "do", "else", "elsif", "end", "ensure", "false", "if",
+        "module", "nil", "require", "rescue", "return", "self", "then", "true", "unless",
+        "until", "when", "while", "yield",
+    ],
+    line_comment: Some("#"),
+    quotes: &['"', '\''],
+};
+
+const SHELL: Syntax = Syntax {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "while",
+    ],
+    line_comment: Some("#"),
+    quotes: &['"', '\''],
+};
+
+const DATA: Syntax = Syntax {
+    keywords: &["true", "false", "null", "yes", "no"],
+    line_comment: Some("#"),
+    quotes: &['"', '\''],
+};
+
+const JSON: Syntax = Syntax {
+    keywords: &["true", "false", "null"],
+    line_comment: None,
+    quotes: &['"'],
+};
+
+fn syntax_for(lang: &str) -> Option<&'static Syntax> {
+    match lang.to_lowercase().as_str() {
+        "rust" | "rs" => Some(&RUST),
+        "js" | "javascript" | "jsx" | "ts" | "typescript" | "tsx" | "mjs" => Some(&JS),
+        "go" | "golang" => Some(&GO),
+        "c" | "cpp" | "c++" | "java" | "swift" | "kotlin" | "cs" | "csharp" | "php" => Some(&C_LIKE),
+        "python" | "py" => Some(&PYTHON),
+        "ruby" | "rb" => Some(&RUBY),
+        "sh" | "bash" | "zsh" | "shell" | "console" | "fish" => Some(&SHELL),
+        "yaml" | "yml" | "toml" | "ini" => Some(&DATA),
+        "json" => Some(&JSON),
+        _ => None,
+    }
+}
+
+/// Base style for code text
+pub fn code_style() -> Style {
+    Style::default().fg(Color::Gray)
+}
+
+/// Highlight a single line of code for the given fence language.
+/// Unknown languages are returned as a single plain span.
+pub fn highlight_line(line: &str, lang: &str) -> Vec<Span<'static>> {
+    let Some(syntax) = syntax_for(lang) else {
+        return vec![Span::styled(line.to_string(), code_style())];
+    };
+
+    let keyword_style = Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD);
+    let string_style = Style::default().fg(Color::Green);
+    let number_style = Style::default().fg(Color::Yellow);
+    let comment_style = Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC);
+
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(plain), code_style()));
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Line comment runs to end of line
+        if let Some(marker) = syntax.line_comment {
+            let rest: String = chars[i..].iter().collect();
+            if rest.starts_with(marker) {
+                flush(&mut plain, &mut spans);
+                spans.push(Span::styled(rest, comment_style));
+                return spans;
+            }
+        }
+
+        // String literal (unterminated strings run to end of line)
+        if syntax.quotes.contains(&c) {
+            flush(&mut plain, &mut spans);
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            spans.push(Span::styled(chars[start..i].iter().collect::<String>(), string_style));
+            continue;
+        }
+
+        // Identifier or keyword
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if syntax.keywords.contains(&word.as_str()) {
+                flush(&mut plain, &mut spans);
+                spans.push(Span::styled(word, keyword_style));
+            } else {
+                plain.push_str(&word);
+            }
+            continue;
+        }
+
+        // Number literal
+        if c.is_ascii_digit() {
+            flush(&mut plain, &mut spans);
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+                i += 1;
+            }
+            spans.push(Span::styled(chars[start..i].iter().collect::<String>(), number_style));
+            continue;
+        }
+
+        plain.push(c);
+        i += 1;
+    }
+
+    flush(&mut plain, &mut spans);
+    spans
+}
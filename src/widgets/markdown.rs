@@ -0,0 +1,529 @@
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::highlight::highlight_line;
+
+// Private-use characters that bracket a shortcode index while the markdown is parsed
+const TOKEN_OPEN: char = '\u{E000}';
+const TOKEN_CLOSE: char = '\u{E001}';
+
+/// Matches Hugo shortcodes (`{{< >}}`, `{{% %}}`) and Liquid tags/output (`{% %}`, `{{ }}`).
+/// Tokens never span lines, so placeholders keep source line numbers intact.
+fn template_token_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{\{[<%].*?[%>]\}\}|\{%.*?%\}|\{\{.*?\}\}").unwrap())
+}
+
+fn placeholder_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new("\u{E000}(\\d+)\u{E001}").unwrap())
+}
+
+fn shortcode_style(token: &str) -> Style {
+    if token.starts_with("{{<") || token.starts_with("{{%") {
+        // Hugo shortcode
+        Style::default().fg(Color::LightMagenta).add_modifier(Modifier::BOLD)
+    } else {
+        // Liquid tag or output
+        Style::default().fg(Color::LightYellow)
+    }
+}
+
+fn heading_style(level: HeadingLevel) -> Style {
+    match level {
+        HeadingLevel::H1 => Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        HeadingLevel::H2 => Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        HeadingLevel::H3 => Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD),
+        _ => Style::default().fg(Color::Blue),
+    }
+}
+
+fn dim() -> Style {
+    Style::default().fg(Color::DarkGray)
+}
+
+/// Styled lines for the content pane, with the line index of every heading
+pub struct Rendered {
+    pub lines: Vec<Line<'static>>,
+    pub headings: Vec<usize>,
+    pub source_lines: Vec<usize>, // Body line (0-based) each rendered line came from
+}
+
+/// A line with no content besides blockquote bars
+fn is_blank(line: &Line) -> bool {
+    line.spans.iter().all(|s| s.content.trim_matches(['▎', ' ']).is_empty())
+}
+
+#[derive(Default)]
+struct TableState {
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<Vec<Span<'static>>>>,
+    header_rows: usize,
+}
+
+/// Converts markdown events into styled terminal lines
+struct Renderer {
+    lines: Vec<Line<'static>>,
+    headings: Vec<usize>,
+    source_lines: Vec<usize>,
+    line_starts: Vec<usize>, // Byte offset of each source line
+    offset: usize, // Source offset of the event being rendered
+    current: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    tokens: Vec<String>,
+    // One entry per open list: next number for ordered lists
+    lists: Vec<Option<u64>>,
+    // Marker for the first line of the current list item
+    pending_marker: Option<String>,
+    quote_depth: usize,
+    code: Option<(String, String)>, // (language, buffered text)
+    links: Vec<String>,
+    table: Option<TableState>,
+}
+
+impl Renderer {
+    fn new(source: &str, tokens: Vec<String>) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            lines: Vec::new(),
+            headings: Vec::new(),
+            source_lines: Vec::new(),
+            line_starts,
+            offset: 0,
+            current: Vec::new(),
+            styles: vec![Style::default()],
+            tokens,
+            lists: Vec::new(),
+            pending_marker: None,
+            quote_depth: 0,
+            code: None,
+            links: Vec::new(),
+            table: None,
+        }
+    }
+
+    fn source_line(&self) -> usize {
+        self.line_starts.partition_point(|&start| start <= self.offset).saturating_sub(1)
+    }
+
+    fn push_line(&mut self, line: Line<'static>) {
+        let source = self.source_line();
+        self.push_line_from(line, source);
+    }
+
+    fn push_line_from(&mut self, line: Line<'static>, source: usize) {
+        self.lines.push(line);
+        self.source_lines.push(source);
+    }
+
+    fn pop_line(&mut self) {
+        self.lines.pop();
+        self.source_lines.pop();
+    }
+
+    fn style(&self) -> Style {
+        *self.styles.last().unwrap_or(&Style::default())
+    }
+
+    fn push_style(&mut self, modify: impl FnOnce(Style) -> Style) {
+        let style = modify(self.style());
+        self.styles.push(style);
+    }
+
+    fn pop_style(&mut self) {
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+    }
+
+    /// Restore shortcode placeholders to their original text
+    fn restore(&self, text: &str) -> String {
+        placeholder_re()
+            .replace_all(text, |caps: &regex::Captures| {
+                let idx: usize = caps[1].parse().unwrap_or(usize::MAX);
+                self.tokens.get(idx).cloned().unwrap_or_default()
+            })
+            .into_owned()
+    }
+
+    /// Append text in the given style, splitting out shortcode tokens
+    fn push_text(&mut self, text: &str, style: Style) {
+        let mut last = 0;
+        for caps in placeholder_re().captures_iter(text) {
+            let whole = caps.get(0).unwrap();
+            if whole.start() > last {
+                self.current.push(Span::styled(text[last..whole.start()].to_string(), style));
+            }
+            let idx: usize = caps[1].parse().unwrap_or(usize::MAX);
+            if let Some(token) = self.tokens.get(idx) {
+                self.current.push(Span::styled(token.clone(), shortcode_style(token)));
+            }
+            last = whole.end();
+        }
+        if last < text.len() {
+            self.current.push(Span::styled(text[last..].to_string(), style));
+        }
+    }
+
+    /// Prefix spans for the next line (blockquote bars and list indentation)
+    fn prefix(&mut self) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        if self.quote_depth > 0 {
+            spans.push(Span::styled("▎ ".repeat(self.quote_depth), dim()));
+        }
+        if !self.lists.is_empty() {
+            let indent = "  ".repeat(self.lists.len() - 1);
+            match self.pending_marker.take() {
+                Some(marker) => {
+                    spans.push(Span::raw(indent));
+                    spans.push(Span::styled(marker, Style::default().fg(Color::Yellow)));
+                }
+                None => spans.push(Span::raw(format!("{}  ", indent))),
+            }
+        }
+        spans
+    }
+
+    fn flush_line(&mut self) {
+        if self.current.is_empty() && self.pending_marker.is_none() {
+            return;
+        }
+        let mut spans = self.prefix();
+        spans.append(&mut self.current);
+        self.push_line(Line::from(spans));
+    }
+
+    fn blank_line(&mut self) {
+        self.flush_line();
+        if self.lines.last().is_some_and(|l| !is_blank(l)) {
+            if self.quote_depth > 0 {
+                let prefix = self.prefix();
+                self.push_line(Line::from(prefix));
+            } else {
+                self.push_line(Line::default());
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => {}
+            Tag::Heading { level, .. } => {
+                self.blank_line();
+                self.headings.push(self.lines.len());
+                self.push_style(|_| heading_style(level));
+            }
+            Tag::BlockQuote(_) => {
+                self.flush_line();
+                self.quote_depth += 1;
+                self.push_style(|s| s.add_modifier(Modifier::ITALIC));
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush_line();
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or("").to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code = Some((lang, String::new()));
+            }
+            Tag::List(start) => {
+                self.flush_line();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush_line();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        let marker = format!("{}. ", n);
+                        *n += 1;
+                        marker
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.pending_marker = Some(marker);
+            }
+            Tag::Table(alignments) => {
+                self.blank_line();
+                self.table = Some(TableState { alignments, ..Default::default() });
+            }
+            Tag::TableHead => {
+                if let Some(table) = self.table.as_mut() {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::TableRow => {
+                if let Some(table) = self.table.as_mut() {
+                    table.rows.push(Vec::new());
+                }
+            }
+            Tag::TableCell => self.current.clear(),
+            Tag::Emphasis => self.push_style(|s| s.add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(|s| s.add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.push_style(|s| s.add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } => {
+                self.links.push(self.restore(&dest_url));
+                self.push_style(|s| s.fg(Color::Blue).add_modifier(Modifier::UNDERLINED));
+            }
+            Tag::Image { dest_url, .. } => {
+                self.links.push(self.restore(&dest_url));
+                self.current.push(Span::styled("[image: ", dim()));
+                self.push_style(|s| s.fg(Color::Blue));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                if self.lists.is_empty() {
+                    self.blank_line();
+                } else {
+                    self.flush_line();
+                }
+            }
+            TagEnd::Heading(_) => {
+                self.pop_style();
+                self.flush_line();
+                self.push_line(Line::default());
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush_line();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.pop_style();
+                // Drop the quote's own trailing separator so it doesn't leave a dangling bar
+                if self.lines.last().is_some_and(is_blank) {
+                    self.pop_line();
+                }
+                self.blank_line();
+            }
+            TagEnd::CodeBlock => {
+                if let Some((lang, text)) = self.code.take() {
+                    let text = self.restore(&text);
+                    let label = if lang.is_empty() { "code".to_string() } else { lang.clone() };
+                    let mut header = self.prefix();
+                    header.push(Span::styled(format!("┌─ {}", label), dim()));
+                    let fence_line = self.source_line();
+                    self.push_line(Line::from(header));
+                    for (i, line) in text.trim_end_matches('\n').lines().enumerate() {
+                        let mut spans = self.prefix();
+                        spans.push(Span::styled("│ ", dim()));
+                        spans.extend(highlight_line(line, &lang));
+                        self.push_line_from(Line::from(spans), fence_line + 1 + i);
+                    }
+                    let mut footer = self.prefix();
+                    footer.push(Span::styled("└─", dim()));
+                    self.push_line(Line::from(footer));
+                }
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::List(_) => {
+                self.flush_line();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::Item => self.flush_line(),
+            TagEnd::TableHead => {
+                if let Some(table) = self.table.as_mut() {
+                    table.header_rows = table.rows.len();
+                }
+            }
+            TagEnd::TableCell => {
+                let cell = std::mem::take(&mut self.current);
+                if let Some(row) = self.table.as_mut().and_then(|t| t.rows.last_mut()) {
+                    row.push(cell);
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+                self.blank_line();
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_style(),
+            TagEnd::Link => {
+                self.pop_style();
+                if let Some(url) = self.links.pop() {
+                    let text: String = self.current.iter().map(|s| s.content.as_ref()).collect();
+                    if !text.ends_with(&url) {
+                        self.current.push(Span::styled(format!(" ({})", url), dim()));
+                    }
+                }
+            }
+            TagEnd::Image => {
+                self.pop_style();
+                let url = self.links.pop().unwrap_or_default();
+                self.current.push(Span::styled(format!("]({})", url), dim()));
+            }
+            _ => {}
+        }
+    }
+
+    fn render_table(&mut self, table: TableState) {
+        let columns = table.rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let mut widths = vec![0usize; columns];
+        for row in &table.rows {
+            for (i, cell) in row.iter().enumerate() {
+                let width: usize = cell.iter().map(|s| s.width()).sum();
+                widths[i] = widths[i].max(width);
+            }
+        }
+
+        let border = dim();
+        for (r, row) in table.rows.into_iter().enumerate() {
+            let mut spans = self.prefix();
+            for (i, cell) in row.into_iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::styled(" │ ", border));
+                }
+                let width: usize = cell.iter().map(|s| s.width()).sum();
+                let pad = widths[i].saturating_sub(width);
+                let (left, right) = match table.alignments.get(i) {
+                    Some(Alignment::Right) => (pad, 0),
+                    Some(Alignment::Center) => (pad / 2, pad - pad / 2),
+                    _ => (0, pad),
+                };
+                spans.push(Span::raw(" ".repeat(left)));
+                for span in cell {
+                    if r < table.header_rows {
+                        spans.push(Span::styled(span.content, span.style.add_modifier(Modifier::BOLD)));
+                    } else {
+                        spans.push(span);
+                    }
+                }
+                spans.push(Span::raw(" ".repeat(right)));
+            }
+            self.push_line(Line::from(spans));
+
+            if r + 1 == table.header_rows {
+                let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                let mut spans = self.prefix();
+                spans.push(Span::styled(rule.join("─┼─"), border));
+                self.push_line(Line::from(spans));
+            }
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        if let Some((_, buffer)) = self.code.as_mut() {
+            match event {
+                Event::Text(text) => {
+                    buffer.push_str(&text);
+                    return;
+                }
+                Event::End(TagEnd::CodeBlock) => {}
+                _ => return,
+            }
+        }
+
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                let style = self.style();
+                self.push_text(&text, style);
+            }
+            Event::Code(code) => {
+                let code = self.restore(&code);
+                self.current.push(Span::styled(code, Style::default().fg(Color::Yellow)));
+            }
+            Event::Html(html) | Event::InlineHtml(html) => {
+                let html = self.restore(&html);
+                let mut lines = html.split('\n').peekable();
+                while let Some(part) = lines.next() {
+                    if !part.is_empty() {
+                        self.current.push(Span::styled(part.to_string(), dim()));
+                    }
+                    if lines.peek().is_some() {
+                        self.flush_line();
+                    }
+                }
+            }
+            Event::SoftBreak => self.current.push(Span::raw(" ")),
+            Event::HardBreak => self.flush_line(),
+            Event::Rule => {
+                self.blank_line();
+                self.push_line(Line::from(Span::styled("─".repeat(40), dim())));
+                self.push_line(Line::default());
+            }
+            Event::TaskListMarker(checked) => {
+                let marker = if checked { "[x] " } else { "[ ] " };
+                self.current.push(Span::styled(marker, Style::default().fg(Color::Yellow)));
+            }
+            Event::FootnoteReference(name) => {
+                self.current.push(Span::styled(format!("[^{}]", name), dim()));
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Rendered {
+        self.flush_line();
+        while self.lines.last().is_some_and(is_blank) {
+            self.pop_line();
+        }
+        Rendered {
+            lines: self.lines,
+            headings: self.headings,
+            source_lines: self.source_lines,
+        }
+    }
+}
+
+/// Render a markdown body into styled lines for the content pane.
+/// Hugo shortcodes and Liquid tags are kept verbatim and highlighted as tokens.
+pub fn render(source: &str) -> Rendered {
+    // Swap template tokens for placeholders so the markdown parser leaves them intact
+    let mut tokens = Vec::new();
+    let protected = template_token_re().replace_all(source, |caps: &regex::Captures| {
+        tokens.push(caps[0].to_string());
+        format!("{}{}{}", TOKEN_OPEN, tokens.len() - 1, TOKEN_CLOSE)
+    });
+
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+
+    let mut renderer = Renderer::new(&protected, tokens);
+    for (event, range) in Parser::new_ext(&protected, options).into_offset_iter() {
+        renderer.offset = range.start;
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+/// Render a body as raw source, one line per source line
+pub fn render_raw(source: &str) -> Rendered {
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    let lines = source
+        .lines()
+        .enumerate()
+        .map(|(i, l)| {
+            let trimmed = l.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            } else if !in_fence && trimmed.starts_with('#') {
+                headings.push(i);
+            }
+            Line::from(l.to_string())
+        })
+        .collect::<Vec<_>>();
+    let source_lines = (0..lines.len()).collect();
+    Rendered { lines, headings, source_lines }
+}
+
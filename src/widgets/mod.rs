@@ -1 +1,4 @@
-// Placeholder for future widget modules
+pub mod editor;
+pub mod highlight;
+pub mod markdown;
+pub mod wrap;
@@ -0,0 +1,92 @@
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A run of text that wraps as a unit (a word or a stretch of whitespace)
+struct Piece {
+    text: String,
+    style: Style,
+    space: bool,
+}
+
+fn pieces(line: &Line<'static>) -> Vec<Piece> {
+    let mut out: Vec<Piece> = Vec::new();
+    for span in &line.spans {
+        let style = line.style.patch(span.style);
+        for c in span.content.chars() {
+            let space = c.is_whitespace();
+            match out.last_mut() {
+                Some(p) if p.space == space && p.style == style => p.text.push(c),
+                _ => out.push(Piece { text: c.to_string(), style, space }),
+            }
+        }
+    }
+    out
+}
+
+/// Soft-wrap styled lines at word boundaries to fit `width` columns.
+/// Returns the wrapped rows and, for each input line, the index of its first row.
+pub fn wrap_lines(lines: &[Line<'static>], width: usize) -> (Vec<Line<'static>>, Vec<usize>) {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut starts = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        starts.push(rows.len());
+        if line.width() <= width {
+            rows.push(line.clone());
+            continue;
+        }
+
+        let mut row: Vec<Span<'static>> = Vec::new();
+        let mut row_width = 0;
+        for piece in pieces(line) {
+            let piece_width = piece.text.width();
+
+            if row_width + piece_width <= width {
+                row.push(Span::styled(piece.text, piece.style));
+                row_width += piece_width;
+                continue;
+            }
+
+            // Whitespace at a break point is dropped
+            if piece.space {
+                rows.push(Line::from(std::mem::take(&mut row)));
+                row_width = 0;
+                continue;
+            }
+
+            if piece_width <= width {
+                rows.push(Line::from(std::mem::take(&mut row)));
+                row.push(Span::styled(piece.text, piece.style));
+                row_width = piece_width;
+                continue;
+            }
+
+            // Word longer than the pane: hard-break it by character
+            let mut chunk = String::new();
+            for c in piece.text.chars() {
+                let w = c.width().unwrap_or(0);
+                if row_width + w > width {
+                    if !chunk.is_empty() {
+                        row.push(Span::styled(std::mem::take(&mut chunk), piece.style));
+                    }
+                    rows.push(Line::from(std::mem::take(&mut row)));
+                    row_width = 0;
+                }
+                chunk.push(c);
+                row_width += w;
+            }
+            if !chunk.is_empty() {
+                row.push(Span::styled(chunk, piece.style));
+            }
+        }
+        if !row.is_empty() {
+            rows.push(Line::from(row));
+        }
+    }
+
+    (rows, starts)
+}